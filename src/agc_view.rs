@@ -0,0 +1,88 @@
+use eframe::egui;
+use crate::{field_options, SerialApp};
+
+// (name, AGCCTRL2, AGCCTRL1, AGCCTRL0), starting points following the AGCCTRL2/1/0 register descriptions
+// and the "AGC Control" section of the CC2510Fx/CC2511Fx datasheet (TI SWRS055)
+pub const AGC_PRESETS: [(&str, u8, u8, u8); 3] = [
+    ("Default", 0x03, 0x40, 0x91),
+    // all gain available and the highest amplitude target (42 dB instead of 33 dB)
+    ("High sensitivity", 0x07, 0x40, 0x91),
+    // top DVGA step unused, lowest amplitude target (24 dB), WAIT_TIME 16 -> 32 samples and
+    // FILTER_LENGTH 16 -> 32 samples, HYST_LEVEL stays at medium like the default
+    ("Strong interferer", 0x40, 0x40, 0xB2),
+];
+
+pub struct AgcView {
+    pub open: bool,
+}
+
+impl AgcView {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+}
+
+// CARRIER_SENSE_ABS_THR is a 4 bit two's complement value, -8 turns it off
+fn carrier_sense_absolute_threshold_text(value: u8) -> String {
+    let threshold = ((value << 4) as i8) >> 4;
+    match threshold {
+        -8 => "Disabled".to_string(),
+        0 => "At MAGN_TARGET".to_string(),
+        _ => format!("{:+} dB from MAGN_TARGET", threshold),
+    }
+}
+
+impl SerialApp {
+    fn apply_agc_preset(&mut self, name: &str, agcctrl2: u8, agcctrl1: u8, agcctrl0: u8) {
+        let before = self.register_value;
+        self.register_value.agcctrl2 = agcctrl2;
+        self.register_value.agcctrl1 = agcctrl1;
+        self.register_value.agcctrl0 = agcctrl0;
+        self.record_change(before, format!("AGC preset \"{}\"", name));
+    }
+
+    pub(crate) fn show_agc_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.agc_view.open;
+        egui::Window::new("AGC")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Presets");
+                    for (name, agcctrl2, agcctrl1, agcctrl0) in AGC_PRESETS {
+                        let active = self.register_value.agcctrl2 == agcctrl2
+                            && self.register_value.agcctrl1 == agcctrl1
+                            && self.register_value.agcctrl0 == agcctrl0;
+                        if ui.selectable_label(active, name).clicked() {
+                            self.apply_agc_preset(name, agcctrl2, agcctrl1, agcctrl0);
+                        }
+                    }
+                });
+                ui.separator();
+
+                let magn_targets: Vec<String> = field_options::MAGN_TARGETS_DB.iter().map(|db| format!("{} dB", db)).collect();
+                let relative_thresholds: Vec<String> = field_options::CARRIER_SENSE_REL_THRESHOLDS_DB.iter()
+                    .map(|db| if *db == 0 {"Disabled".to_string()} else {format!("+{} dB RSSI increase", db)})
+                    .collect();
+                let absolute_thresholds: Vec<String> = (0..16).map(carrier_sense_absolute_threshold_text).collect();
+                let wait_times: Vec<String> = field_options::WAIT_TIMES.iter().map(|samples| format!("{} samples", samples)).collect();
+                let filter_lengths: Vec<String> = field_options::FILTER_LENGTHS.iter().map(|samples| format!("{} samples", samples)).collect();
+
+                egui::Grid::new("agc_fields")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.register_field_combo(ui, "AGCCTRL2", "Max DVGA gain", &field_options::MAX_DVGA_GAINS, 6, 0x03);
+                        self.register_field_combo(ui, "AGCCTRL2", "Max LNA gain", &field_options::MAX_LNA_GAINS, 3, 0x07);
+                        self.register_field_combo(ui, "AGCCTRL2", "Magnitude target", &magn_targets, 0, 0x07);
+                        self.register_field_combo(ui, "AGCCTRL1", "LNA priority", &["Decrease LNA2 gain first", "Decrease LNA gain first"], 6, 0x01);
+                        self.register_field_combo(ui, "AGCCTRL1", "Carrier sense relative", &relative_thresholds, 4, 0x03);
+                        self.register_field_combo(ui, "AGCCTRL1", "Carrier sense absolute", &absolute_thresholds, 0, 0x0F);
+                        self.register_field_combo(ui, "AGCCTRL0", "Hysteresis", &field_options::HYST_LEVELS, 6, 0x03);
+                        self.register_field_combo(ui, "AGCCTRL0", "Wait time", &wait_times, 4, 0x03);
+                        self.register_field_combo(ui, "AGCCTRL0", "Freeze", &field_options::AGC_FREEZE_MODES, 2, 0x03);
+                        self.register_field_combo(ui, "AGCCTRL0", "Filter length", &filter_lengths, 0, 0x03);
+                    });
+            });
+        self.agc_view.open = open;
+    }
+}
@@ -0,0 +1,232 @@
+use eframe::egui;
+use crate::{chips, conversions, format_value, SerialApp};
+
+// Register values that put channel `channr` as close as possible to the target frequency
+#[derive(Clone, Copy)]
+pub struct ChannelSolution {
+    pub freq: u32,
+    pub channr: u8,
+    pub chanspc_e: u8,
+    pub chanspc_m: u8,
+    pub frequency_mhz: f64,
+    pub error_hz: f64,
+}
+
+pub struct ChannelSolverState {
+    pub open: bool,
+    target_frequency: String,
+    channel: u8,
+    use_spacing: bool,
+    spacing: String,
+    solution: Option<ChannelSolution>,
+    message: String,
+}
+
+impl ChannelSolverState {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            target_frequency: "2450.0".to_string(),
+            channel: 0,
+            use_spacing: false,
+            spacing: "199.951".to_string(),
+            solution: None,
+            message: String::new(),
+        }
+    }
+}
+
+fn solve_with_spacing(target_mhz: f64, channr: u8, chanspc_e: u8, chanspc_m: u8, crystal_hz: u32, chip: &chips::ChipProfile) -> Option<ChannelSolution> {
+    let spacing_khz = conversions::register_to_channel_spacing(chanspc_e, chanspc_m, crystal_hz);
+    let base_mhz = target_mhz - channr as f64 * spacing_khz / 1000.0;
+    if !chip.frequency_in_band(base_mhz) {
+        return None;
+    }
+    // nearest FREQ word, the error is at most half a step either way
+    let freq = conversions::frequency_to_register(base_mhz, crystal_hz);
+    let frequency_mhz = conversions::register_to_channel_frequency(freq, channr, chanspc_e, chanspc_m, crystal_hz);
+    Some(ChannelSolution {
+        freq,
+        channr,
+        chanspc_e,
+        chanspc_m,
+        frequency_mhz,
+        error_hz: (frequency_mhz - target_mhz) * 1_000_000.0,
+    })
+}
+
+// With a desired spacing only that spacing is tried, otherwise every CHANSPC setting is tried and
+// the one with the smallest error wins, preferring the spacing already in use on ties
+pub fn solve(target_mhz: f64, channr: u8, spacing_khz: Option<f64>, current_spacing: (u8, u8), crystal_hz: u32, chip: &chips::ChipProfile) -> Option<ChannelSolution> {
+    if let Some(spacing_khz) = spacing_khz {
+        let (chanspc_e, chanspc_m) = conversions::channel_spacing_to_register(spacing_khz, crystal_hz);
+        return solve_with_spacing(target_mhz, channr, chanspc_e, chanspc_m, crystal_hz, chip);
+    }
+
+    let mut best = solve_with_spacing(target_mhz, channr, current_spacing.0, current_spacing.1, crystal_hz, chip);
+    if channr == 0 {
+        return best;
+    }
+    for chanspc_e in 0..4 {
+        for chanspc_m in 0..=255 {
+            if let Some(solution) = solve_with_spacing(target_mhz, channr, chanspc_e, chanspc_m, crystal_hz, chip) {
+                if best.is_none_or(|best| solution.error_hz.abs() < best.error_hz.abs()) {
+                    best = Some(solution);
+                }
+            }
+        }
+    }
+    best
+}
+
+impl SerialApp {
+    fn solve_channel(&mut self) {
+        let state = &mut self.channel_solver;
+        state.solution = None;
+        let Ok(target_mhz) = state.target_frequency.trim().parse::<f64>() else {
+            state.message = "Invalid target frequency".to_string();
+            return;
+        };
+        let spacing_khz = if state.use_spacing {
+            match state.spacing.trim().parse::<f64>() {
+                Ok(spacing) => Some(spacing),
+                Err(_) => {
+                    state.message = "Invalid channel spacing".to_string();
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        let current_spacing = (self.register_value.mdmcfg1 & 0x03, self.register_value.mdmcfg0);
+        state.solution = solve(target_mhz, state.channel, spacing_khz, current_spacing, self.crystal_hz, self.chip);
+        state.message = match state.solution {
+            Some(_) => String::new(),
+            None => format!("No base frequency inside {} MHz reaches channel {} at {} MHz", self.chip.bands_text(), state.channel, target_mhz),
+        };
+    }
+
+    fn apply_channel_solution(&mut self, solution: ChannelSolution) {
+        let before = self.register_value;
+        self.register_value.freq2 = (solution.freq >> 16) as u8;
+        self.register_value.freq1 = (solution.freq >> 8) as u8;
+        self.register_value.freq0 = solution.freq as u8;
+        self.register_value.channr = solution.channr;
+        self.register_value.mdmcfg1 &= 0xFC;
+        self.register_value.mdmcfg1 |= solution.chanspc_e;
+        self.register_value.mdmcfg0 = solution.chanspc_m;
+        self.record_change(before, format!("Channel {} at {} MHz", solution.channr, format_value(solution.frequency_mhz)));
+        self.update_parameters_from_registers();
+    }
+
+    pub(crate) fn show_channel_solver_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.channel_solver.open;
+        let mut apply = None;
+        egui::Window::new("Channel Solver")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let state = &mut self.channel_solver;
+                egui::Grid::new("channel_solver_inputs")
+                    .show(ui, |ui| {
+                        ui.label("Target frequency");
+                        ui.add(egui::TextEdit::singleline(&mut state.target_frequency).desired_width(68.0));
+                        ui.label("MHz");
+                        ui.end_row();
+
+                        ui.label("Channel number");
+                        ui.add(egui::DragValue::new(&mut state.channel).range(0..=255));
+                        ui.end_row();
+
+                        ui.checkbox(&mut state.use_spacing, "Desired spacing");
+                        ui.add_enabled(state.use_spacing, egui::TextEdit::singleline(&mut state.spacing).desired_width(68.0));
+                        ui.label("kHz");
+                        ui.end_row();
+                    });
+
+                if ui.button("Solve").clicked() {
+                    self.solve_channel();
+                }
+                ui.label(&self.channel_solver.message);
+
+                if let Some(solution) = self.channel_solver.solution {
+                    ui.separator();
+                    egui::Grid::new("channel_solver_solution")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("FREQ2/1/0");
+                            ui.label(format!("0x{:02X} 0x{:02X} 0x{:02X}", solution.freq >> 16, (solution.freq >> 8) & 0xFF, solution.freq & 0xFF));
+                            ui.end_row();
+
+                            ui.label("Base frequency");
+                            ui.label(format!("{} MHz", format_value(conversions::register_to_frequency(solution.freq, self.crystal_hz))));
+                            ui.end_row();
+
+                            ui.label("CHANNR");
+                            ui.label(solution.channr.to_string());
+                            ui.end_row();
+
+                            ui.label("CHANSPC_E / CHANSPC_M");
+                            ui.label(format!("{} / {} ({} kHz)", solution.chanspc_e, solution.chanspc_m,
+                                format_value(conversions::register_to_channel_spacing(solution.chanspc_e, solution.chanspc_m, self.crystal_hz))));
+                            ui.end_row();
+
+                            ui.label("Center frequency");
+                            ui.label(format!("{} MHz", solution.frequency_mhz));
+                            ui.end_row();
+
+                            ui.label("Error");
+                            ui.label(format!("{:.1} Hz", solution.error_hz));
+                            ui.end_row();
+                        });
+                    if ui.button("Apply").clicked() {
+                        apply = Some(solution);
+                    }
+                }
+            });
+        if let Some(solution) = apply {
+            self.apply_channel_solution(solution);
+        }
+        self.channel_solver.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XOSC_26_MHZ;
+
+    // the FREQ word can always get within half a step of the target
+    const HALF_FREQ_STEP_HZ: f64 = XOSC_26_MHZ as f64 / 65_536.0 / 2.0;
+
+    #[test]
+    fn channel_zero_only_needs_the_base_frequency() {
+        let solution = solve(2464.0, 0, None, (2, 0xF8), XOSC_26_MHZ, &chips::CC2510).unwrap();
+        assert_eq!(solution.freq, 0x5E_C4EC);
+        assert_eq!((solution.chanspc_e, solution.chanspc_m), (2, 0xF8));
+        assert!(solution.error_hz.abs() <= HALF_FREQ_STEP_HZ);
+    }
+
+    #[test]
+    fn fixed_spacing_is_kept() {
+        let solution = solve(2450.0, 10, Some(199.951), (0, 0), XOSC_26_MHZ, &chips::CC2510).unwrap();
+        assert_eq!((solution.channr, solution.chanspc_e, solution.chanspc_m), (10, 2, 0xF8));
+        assert_eq!(solution.frequency_mhz, conversions::register_to_channel_frequency(solution.freq, 10, 2, 0xF8, XOSC_26_MHZ));
+        assert!(solution.error_hz.abs() <= HALF_FREQ_STEP_HZ);
+    }
+
+    #[test]
+    fn free_spacing_is_at_least_as_good_as_the_current_one() {
+        let current = solve(2450.123, 37, Some(199.951), (2, 0xF8), XOSC_26_MHZ, &chips::CC2510).unwrap();
+        let free = solve(2450.123, 37, None, (2, 0xF8), XOSC_26_MHZ, &chips::CC2510).unwrap();
+        assert!(free.error_hz.abs() <= current.error_hz.abs());
+        assert_eq!(free.channr, 37);
+    }
+
+    #[test]
+    fn base_frequency_outside_the_band_has_no_solution() {
+        assert!(solve(2000.0, 0, None, (2, 0xF8), XOSC_26_MHZ, &chips::CC2510).is_none());
+        // channel 255 at 400 kHz spacing would need a base frequency of 2298.5 MHz
+        assert!(solve(2400.5, 255, Some(400.0), (2, 0xF8), XOSC_26_MHZ, &chips::CC2510).is_none());
+    }
+}
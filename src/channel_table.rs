@@ -0,0 +1,236 @@
+use eframe::egui;
+use crate::{conversions, format_value, SerialApp};
+
+// Limits of one regulatory rule set, only the parts that follow from the register configuration
+pub struct RegulatoryProfile {
+    pub name: &'static str,
+    // allowed frequency ranges in MHz, the whole occupied bandwidth has to stay inside one of them
+    pub bands: &'static [(f64, f64)],
+    pub max_eirp_dbm: f64,
+    pub max_occupied_bandwidth_khz: Option<f64>,
+    // digital modulation rules that ask for a minimum 6 dB bandwidth
+    pub min_occupied_bandwidth_khz: Option<f64>,
+}
+
+pub const REGULATORY_PROFILES: [RegulatoryProfile; 5] = [
+    RegulatoryProfile {
+        name: "FCC 15.247 (digital modulation)",
+        bands: &[(902.0, 928.0), (2400.0, 2483.5)],
+        max_eirp_dbm: 36.0,
+        max_occupied_bandwidth_khz: None,
+        min_occupied_bandwidth_khz: Some(500.0),
+    },
+    RegulatoryProfile {
+        name: "FCC 15.249",
+        bands: &[(902.0, 928.0), (2400.0, 2483.5)],
+        // 50 mV/m at 3 m
+        max_eirp_dbm: -1.2,
+        max_occupied_bandwidth_khz: None,
+        min_occupied_bandwidth_khz: None,
+    },
+    RegulatoryProfile {
+        name: "ETSI EN 300 440 (2.4 GHz)",
+        bands: &[(2400.0, 2483.5)],
+        max_eirp_dbm: 10.0,
+        max_occupied_bandwidth_khz: None,
+        min_occupied_bandwidth_khz: None,
+    },
+    RegulatoryProfile {
+        name: "Japan ARIB STD-T66 (2.4 GHz)",
+        bands: &[(2400.0, 2483.5)],
+        // 10 mW/MHz, taken as the total for signals narrower than 1 MHz
+        max_eirp_dbm: 12.15,
+        max_occupied_bandwidth_khz: Some(26_000.0),
+        min_occupied_bandwidth_khz: None,
+    },
+    RegulatoryProfile {
+        name: "Japan ARIB STD-T108 (920 MHz)",
+        bands: &[(920.5, 929.7)],
+        max_eirp_dbm: 16.0,
+        // five 200 kHz unit channels at most
+        max_occupied_bandwidth_khz: Some(1_000.0),
+        min_occupied_bandwidth_khz: None,
+    },
+];
+
+impl RegulatoryProfile {
+    pub fn channel_allowed(&self, center_mhz: f64, occupied_bandwidth_khz: f64) -> bool {
+        let half_bandwidth_mhz = occupied_bandwidth_khz / 2000.0;
+        self.bands.iter().any(|(min, max)| center_mhz - half_bandwidth_mhz >= *min && center_mhz + half_bandwidth_mhz <= *max)
+    }
+
+    // Every limit the configuration breaks, an empty list means it complies
+    pub fn violations(&self, center_mhz: f64, occupied_bandwidth_khz: f64, eirp_dbm: f64) -> Vec<String> {
+        let mut violations = Vec::new();
+        if !self.channel_allowed(center_mhz, occupied_bandwidth_khz) {
+            violations.push(format!("{} MHz ± {} kHz is outside the allowed bands", format_value(center_mhz), format_value(occupied_bandwidth_khz / 2.0)));
+        }
+        if eirp_dbm > self.max_eirp_dbm {
+            violations.push(format!("EIRP {} dBm is above the {} dBm limit", format_value(eirp_dbm), self.max_eirp_dbm));
+        }
+        if let Some(max) = self.max_occupied_bandwidth_khz {
+            if occupied_bandwidth_khz > max {
+                violations.push(format!("Occupied bandwidth {} kHz is above the {} kHz limit", format_value(occupied_bandwidth_khz), max));
+            }
+        }
+        if let Some(min) = self.min_occupied_bandwidth_khz {
+            if occupied_bandwidth_khz < min {
+                violations.push(format!("Occupied bandwidth {} kHz is below the {} kHz minimum", format_value(occupied_bandwidth_khz), min));
+            }
+        }
+        violations
+    }
+}
+
+pub struct ChannelTableState {
+    pub open: bool,
+    profile: usize,
+    antenna_gain_dbi: f64,
+    only_valid: bool,
+}
+
+impl ChannelTableState {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            profile: 0,
+            antenna_gain_dbi: 0.0,
+            only_valid: false,
+        }
+    }
+}
+
+impl SerialApp {
+    fn select_channel(&mut self, channel: u8) {
+        let before = self.register_value;
+        self.register_value.channr = channel;
+        self.record_change(before, format!("Channel number {} → {}", before.channr, channel));
+        self.update_parameters_from_registers();
+    }
+
+    pub(crate) fn show_channel_table_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.channel_table.open;
+        let mut selected_channel = None;
+        egui::Window::new("Channel Table")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                let state = &mut self.channel_table;
+                ui.horizontal(|ui| {
+                    ui.label("Regulatory profile");
+                    egui::ComboBox::from_id_salt("regulatory_profile")
+                        .selected_text(REGULATORY_PROFILES[state.profile].name)
+                        .show_ui(ui, |ui| {
+                            for (index, profile) in REGULATORY_PROFILES.iter().enumerate() {
+                                ui.selectable_value(&mut state.profile, index, profile.name);
+                            }
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Antenna gain");
+                    ui.add(egui::DragValue::new(&mut state.antenna_gain_dbi).speed(0.1).suffix(" dBi"));
+                    ui.checkbox(&mut state.only_valid, "Only usable channels");
+                });
+
+                let profile = &REGULATORY_PROFILES[self.channel_table.profile];
+                let occupied_bandwidth = conversions::signal_bandwidth(self.dr(), self.register_value.effective_deviation(self.crystal_hz));
+                let tx_setting = self.tx_power_setting();
+                ui.separator();
+
+                let eirp = tx_setting.dbm + self.channel_table.antenna_gain_dbi;
+                let violations = profile.violations(self.channel_frequency(), occupied_bandwidth, eirp);
+                if violations.is_empty() {
+                    ui.label(format!("Channel {} complies with {}", self.register_value.channr, profile.name));
+                }
+                for violation in violations {
+                    ui.colored_label(ui.visuals().error_fg_color, violation);
+                }
+                if !tx_setting.characterized {
+                    ui.colored_label(ui.visuals().warn_fg_color, format!("The PA_TABLE entry in use is not a characterized setting, EIRP is checked for an estimated {:.1} dBm", tx_setting.dbm));
+                }
+                ui.label(format!("Occupied bandwidth (data rate + 2 × deviation): {} kHz", format_value(occupied_bandwidth)));
+                ui.separator();
+
+                let base_frequency = self.base_frequency();
+                let spacing = self.channel_spacing();
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("channel_table")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("CHANNR");
+                            ui.strong("Center frequency");
+                            ui.strong(self.chip.name);
+                            ui.strong("Regulatory");
+                            ui.label("");
+                            ui.end_row();
+
+                            for channel in 0..=255_u8 {
+                                let frequency = conversions::channel_frequency(base_frequency, channel, spacing);
+                                let in_band = self.chip.frequency_in_band(frequency);
+                                let allowed = profile.channel_allowed(frequency, occupied_bandwidth);
+                                if self.channel_table.only_valid && !(in_band && allowed) {
+                                    continue;
+                                }
+                                let text = format!("{} MHz", format_value(frequency));
+                                if channel == self.register_value.channr {
+                                    ui.strong(channel.to_string());
+                                    ui.strong(text);
+                                } else {
+                                    ui.label(channel.to_string());
+                                    ui.label(text);
+                                }
+                                for ok in [in_band, allowed] {
+                                    if ok {
+                                        ui.label("in band");
+                                    } else {
+                                        ui.colored_label(ui.visuals().warn_fg_color, "outside");
+                                    }
+                                }
+                                if ui.add_enabled(in_band && channel != self.register_value.channr, egui::Button::new("Use")).clicked() {
+                                    selected_channel = Some(channel);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        if let Some(channel) = selected_channel {
+            self.select_channel(channel);
+        }
+        self.channel_table.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> &'static RegulatoryProfile {
+        REGULATORY_PROFILES.iter().find(|profile| profile.name.starts_with(name)).unwrap()
+    }
+
+    #[test]
+    fn channel_inside_the_band_complies() {
+        assert!(profile("ETSI").violations(2440.0, 210.0, 0.0).is_empty());
+        assert!(profile("FCC 15.247").violations(915.0, 600.0, 30.0).is_empty());
+    }
+
+    #[test]
+    fn occupied_bandwidth_over_the_band_edge_is_flagged() {
+        let etsi = profile("ETSI");
+        // the center is inside the band, the upper half of the signal isn't
+        assert!(etsi.channel_allowed(2483.3, 100.0));
+        assert!(!etsi.channel_allowed(2483.4, 300.0));
+        assert_eq!(etsi.violations(2483.4, 300.0, 0.0), ["2483.4 MHz ± 150 kHz is outside the allowed bands"]);
+        assert!(!etsi.channel_allowed(2400.05, 200.0));
+    }
+
+    #[test]
+    fn power_and_bandwidth_limits() {
+        assert_eq!(profile("ETSI").violations(2440.0, 210.0, 12.0), ["EIRP 12 dBm is above the 10 dBm limit"]);
+        assert_eq!(profile("FCC 15.247").violations(2440.0, 210.0, 0.0), ["Occupied bandwidth 210 kHz is below the 500 kHz minimum"]);
+        assert_eq!(profile("Japan ARIB STD-T108").violations(925.0, 1200.0, 0.0), ["Occupied bandwidth 1200 kHz is above the 1000 kHz limit"]);
+        // 15.249 allows only about -1.2 dBm EIRP
+        assert_eq!(profile("FCC 15.249").violations(2300.0, 210.0, 0.0).len(), 2);
+    }
+}
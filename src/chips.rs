@@ -0,0 +1,484 @@
+use crate::structs::{RegisterAddress, StatusRegisterAddress};
+
+// One row of a datasheet output power table
+#[derive(Clone, Copy, PartialEq)]
+pub struct PaSetting {
+    pub dbm: i8,
+    pub value: u8,
+    // typical supply current while transmitting
+    pub current_ma: f64,
+}
+
+// Output power of any PA byte. Bytes the datasheet lists are exact, any other byte is interpolated
+// between the listed bytes either side of it, the power isn't monotonic in the byte so that is only an estimate
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PaEstimate {
+    pub dbm: f64,
+    pub current_ma: f64,
+    pub characterized: bool,
+}
+
+impl PaEstimate {
+    pub fn text(&self) -> String {
+        if self.characterized {
+            format!("{} dBm, {} mA", self.dbm, self.current_ma)
+        } else {
+            format!("≈ {:.1} dBm, {:.1} mA (estimate)", self.dbm, self.current_ma)
+        }
+    }
+}
+
+// The output power table measured for base frequencies between `min_mhz` and `max_mhz`
+pub struct PaCharacterization {
+    pub min_mhz: f64,
+    pub max_mhz: f64,
+    pub settings: &'static [PaSetting],
+}
+
+const fn pa(dbm: i8, value: u8, current_ma: f64) -> PaSetting {
+    PaSetting { dbm, value, current_ma }
+}
+
+// Everything that differs between the supported TI radios
+pub struct ChipProfile {
+    pub name: &'static str,
+    // value read back from the PARTNUM status register
+    pub partnum: u8,
+    // usual reference frequency for this chip (after the /2 divider on the USB parts), can be overridden per configuration
+    pub default_crystal_hz: u32,
+    // allowed base frequency ranges in MHz
+    pub bands: &'static [(f64, f64)],
+    // the datasheet's recommended output power settings for each frequency range
+    pub pa_tables: &'static [PaCharacterization],
+    pub register_address: RegisterAddress,
+    pub status_address: StatusRegisterAddress,
+    // RSSI_offset in dB, typical value from the datasheet
+    pub rssi_offset: f64,
+    // (kBaud, dBm) typical sensitivity from the datasheet's RX specifications, slowest rate first
+    pub sensitivity: &'static [(f64, f64)],
+    pub has_usb: bool,
+    pub supports_ask_ook: bool,
+}
+
+impl ChipProfile {
+    pub fn frequency_in_band(&self, frequency: f64) -> bool {
+        self.bands.iter().any(|(min, max)| (*min..=*max).contains(&frequency))
+    }
+
+    pub fn bands_text(&self) -> String {
+        self.bands
+            .iter()
+            .map(|(min, max)| format!("{:?} to {:?}", min, max))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    // Sensitivity of the slowest listed rate that is at least `data_rate_kbaud`, so the estimate errs on the safe side
+    pub fn sensitivity_for_data_rate(&self, data_rate_kbaud: f64) -> Option<(f64, f64)> {
+        self.sensitivity
+            .iter()
+            .find(|(rate, _)| *rate >= data_rate_kbaud)
+            .or(self.sensitivity.last())
+            .copied()
+    }
+
+    // Output power table for a frequency, outside every characterized range the closest range is used
+    pub fn pa_settings(&self, frequency_mhz: f64) -> &'static [PaSetting] {
+        self.pa_tables
+            .iter()
+            .min_by(|a, b| range_distance(a, frequency_mhz).total_cmp(&range_distance(b, frequency_mhz)))
+            .map_or(&[], |table| table.settings)
+    }
+
+    pub fn pa_value_for_dbm(&self, dbm: i8, frequency_mhz: f64) -> Option<u8> {
+        self.pa_settings(frequency_mhz).iter().find(|setting| setting.dbm == dbm).map(|setting| setting.value)
+    }
+
+    pub fn pa_setting_for_value(&self, value: u8, frequency_mhz: f64) -> Option<PaSetting> {
+        self.pa_settings(frequency_mhz).iter().find(|setting| setting.value == value).copied()
+    }
+
+    pub fn estimate_pa_setting(&self, value: u8, frequency_mhz: f64) -> PaEstimate {
+        let settings = self.pa_settings(frequency_mhz);
+        if let Some(setting) = self.pa_setting_for_value(value, frequency_mhz) {
+            return PaEstimate { dbm: setting.dbm as f64, current_ma: setting.current_ma, characterized: true };
+        }
+        let below = settings.iter().filter(|setting| setting.value < value).max_by_key(|setting| setting.value);
+        let above = settings.iter().filter(|setting| setting.value > value).min_by_key(|setting| setting.value);
+        let (dbm, current_ma) = match (below, above) {
+            (Some(below), Some(above)) => {
+                let t = (value - below.value) as f64 / (above.value - below.value) as f64;
+                (below.dbm as f64 + t * (above.dbm - below.dbm) as f64, below.current_ma + t * (above.current_ma - below.current_ma))
+            }
+            // past either end of the table the closest listed byte is used
+            (Some(setting), None) | (None, Some(setting)) => (setting.dbm as f64, setting.current_ma),
+            (None, None) => (0.0, 0.0),
+        };
+        PaEstimate { dbm, current_ma, characterized: false }
+    }
+
+    // The characterized setting closest to `dbm`
+    pub fn nearest_pa_setting(&self, dbm: f64, frequency_mhz: f64) -> PaSetting {
+        self.pa_settings(frequency_mhz)
+            .iter()
+            .min_by(|a, b| (a.dbm as f64 - dbm).abs().total_cmp(&(b.dbm as f64 - dbm).abs()))
+            .copied()
+            .unwrap_or(pa(0, 0x00, 0.0))
+    }
+
+    // The registers that can be read one at a time, registers sharing an address with an earlier one
+    // (PA_TABLE1-7 on the SPI parts) would only read back that earlier register's value
+    pub fn readable_registers(&self) -> Vec<(&'static str, u16)> {
+        let mut registers: Vec<(&'static str, u16)> = Vec::new();
+        for (name, address) in self.register_address.fields() {
+            if !registers.iter().any(|(_, read)| *read == address) {
+                registers.push((name, address));
+            }
+        }
+        registers
+    }
+
+    pub fn pa_dbm_range(&self, frequency_mhz: f64) -> (i8, i8) {
+        let settings = self.pa_settings(frequency_mhz);
+        let min = settings.iter().map(|setting| setting.dbm).min().unwrap_or(0);
+        let max = settings.iter().map(|setting| setting.dbm).max().unwrap_or(0);
+        (min, max)
+    }
+}
+
+// The SoC parts (CC251x, CC111x) map the radio registers into XDATA at 0xDF00
+const SOC_REGISTER_ADDRESS: RegisterAddress = RegisterAddress {
+    iocfg2: 0xDF2F,
+    iocfg1: 0xDF30,
+    iocfg0: 0xDF31,
+    sync1: 0xDF00,
+    sync0: 0xDF01,
+    pktlen: 0xDF02,
+    pktctrl1: 0xDF03,
+    pktctrl0: 0xDF04,
+    addr: 0xDF05,
+    channr: 0xDF06,
+    fsctrl1: 0xDF07,
+    fsctrl0: 0xDF08,
+    freq2: 0xDF09,
+    freq1: 0xDF0A,
+    freq0: 0xDF0B,
+    mdmcfg4: 0xDF0C,
+    mdmcfg3: 0xDF0D,
+    mdmcfg2: 0xDF0E,
+    mdmcfg1: 0xDF0F,
+    mdmcfg0: 0xDF10,
+    deviatn: 0xDF11,
+    mcsm2: 0xDF12,
+    mcsm1: 0xDF13,
+    mcsm0: 0xDF14,
+    foccfg: 0xDF15,
+    bscfg: 0xDF16,
+    agcctrl2: 0xDF17,
+    agcctrl1: 0xDF18,
+    agcctrl0: 0xDF19,
+    frend1: 0xDF1A,
+    frend0: 0xDF1B,
+    fscal3: 0xDF1C,
+    fscal2: 0xDF1D,
+    fscal1: 0xDF1E,
+    fscal0: 0xDF1F,
+    test2: 0xDF23,
+    test1: 0xDF24,
+    test0: 0xDF25,
+    pa_table0: 0xDF2E,
+    pa_table1: 0xDF2D,
+    pa_table2: 0xDF2C,
+    pa_table3: 0xDF2B,
+    pa_table4: 0xDF2A,
+    pa_table5: 0xDF29,
+    pa_table6: 0xDF28,
+    pa_table7: 0xDF27,
+};
+
+const SOC_STATUS_ADDRESS: StatusRegisterAddress = StatusRegisterAddress {
+    partnum: 0xDF36,
+    version: 0xDF37,
+    freqest: 0xDF38,
+    lqi: 0xDF39,
+    rssi: 0xDF3A,
+    marcstate: 0xDF3B,
+    pktstatus: 0xDF3C,
+};
+
+// The CC2500 is a transceiver only, its registers are SPI addresses
+const SPI_REGISTER_ADDRESS: RegisterAddress = RegisterAddress {
+    iocfg2: 0x00,
+    iocfg1: 0x01,
+    iocfg0: 0x02,
+    sync1: 0x04,
+    sync0: 0x05,
+    pktlen: 0x06,
+    pktctrl1: 0x07,
+    pktctrl0: 0x08,
+    addr: 0x09,
+    channr: 0x0A,
+    fsctrl1: 0x0B,
+    fsctrl0: 0x0C,
+    freq2: 0x0D,
+    freq1: 0x0E,
+    freq0: 0x0F,
+    mdmcfg4: 0x10,
+    mdmcfg3: 0x11,
+    mdmcfg2: 0x12,
+    mdmcfg1: 0x13,
+    mdmcfg0: 0x14,
+    deviatn: 0x15,
+    mcsm2: 0x16,
+    mcsm1: 0x17,
+    mcsm0: 0x18,
+    foccfg: 0x19,
+    bscfg: 0x1A,
+    agcctrl2: 0x1B,
+    agcctrl1: 0x1C,
+    agcctrl0: 0x1D,
+    frend1: 0x21,
+    frend0: 0x22,
+    fscal3: 0x23,
+    fscal2: 0x24,
+    fscal1: 0x25,
+    fscal0: 0x26,
+    test2: 0x2C,
+    test1: 0x2D,
+    test0: 0x2E,
+    // PATABLE is a single address on the SPI parts. Its index counter goes back to 0 whenever CSn goes
+    // high, so a single read (which is all the bridge does) always returns entry 0, see readable_registers
+    pa_table0: 0x3E,
+    pa_table1: 0x3E,
+    pa_table2: 0x3E,
+    pa_table3: 0x3E,
+    pa_table4: 0x3E,
+    pa_table5: 0x3E,
+    pa_table6: 0x3E,
+    pa_table7: 0x3E,
+};
+
+const SPI_STATUS_ADDRESS: StatusRegisterAddress = StatusRegisterAddress {
+    partnum: 0x30,
+    version: 0x31,
+    freqest: 0x32,
+    lqi: 0x33,
+    rssi: 0x34,
+    marcstate: 0x35,
+    pktstatus: 0x38,
+};
+
+const BAND_2400: &[(f64, f64)] = &[(2400.0, 2483.5)];
+const BANDS_SUB_GHZ: &[(f64, f64)] = &[(300.0, 348.0), (391.0, 464.0), (782.0, 928.0)];
+
+// Currents are typical values read off the datasheets' TX current figures, -55 dBm is the PA switched off
+const PA_SETTINGS_CC2510: &[PaSetting] = &[
+    pa(1, 0xFF, 28.0), pa(0, 0xFE, 26.0), pa(-2, 0xBF, 24.5), pa(-4, 0xAA, 23.0), pa(-6, 0x7F, 21.5), pa(-8, 0x99, 20.5),
+    pa(-10, 0xCB, 19.5), pa(-12, 0x95, 18.5), pa(-14, 0x59, 18.0), pa(-16, 0x87, 17.5), pa(-18, 0xC8, 17.0), pa(-20, 0xC1, 16.5),
+    pa(-22, 0x83, 16.0), pa(-24, 0x53, 15.5), pa(-26, 0x54, 15.5), pa(-28, 0x41, 15.0), pa(-30, 0x44, 15.0), pa(-55, 0x00, 13.0),
+];
+
+const PA_SETTINGS_CC2500: &[PaSetting] = &[
+    pa(1, 0xFF, 21.5), pa(0, 0xFE, 21.2), pa(-2, 0xBB, 19.9), pa(-4, 0xA9, 18.7), pa(-6, 0x7F, 17.5), pa(-8, 0x6E, 16.5),
+    pa(-10, 0x97, 15.7), pa(-12, 0xC6, 15.0), pa(-14, 0x8D, 14.3), pa(-16, 0x55, 13.8), pa(-18, 0x93, 13.3), pa(-20, 0x46, 12.9),
+    pa(-22, 0x81, 12.5), pa(-24, 0x84, 12.2), pa(-26, 0xC0, 12.0), pa(-28, 0x44, 11.8), pa(-30, 0x50, 11.6), pa(-55, 0x00, 10.0),
+];
+
+const PA_SETTINGS_CC1110_433: &[PaSetting] = &[
+    pa(10, 0xC0, 29.0), pa(7, 0xC8, 25.0), pa(5, 0x84, 21.0), pa(0, 0x60, 16.0), pa(-10, 0x34, 13.5),
+    pa(-15, 0x1D, 13.0), pa(-20, 0x0E, 12.5), pa(-30, 0x12, 12.0), pa(-55, 0x00, 11.0),
+];
+
+const PA_SETTINGS_CC1110_868: &[PaSetting] = &[
+    pa(10, 0xC2, 33.5), pa(7, 0xCB, 27.5), pa(5, 0x81, 24.5), pa(0, 0x50, 17.0), pa(-5, 0x2C, 15.0), pa(-10, 0x27, 14.0),
+    pa(-15, 0x1E, 13.5), pa(-20, 0x0F, 13.0), pa(-30, 0x03, 12.5), pa(-55, 0x00, 11.5),
+];
+
+const SENSITIVITY_CC2510: &[(f64, f64)] = &[(2.4, -103.0), (10.0, -101.0), (250.0, -88.0), (500.0, -83.0)];
+const SENSITIVITY_CC2500: &[(f64, f64)] = &[(2.4, -104.0), (10.0, -101.0), (250.0, -89.0), (500.0, -82.0)];
+// 868 MHz figures
+const SENSITIVITY_CC1110: &[(f64, f64)] = &[(1.2, -110.0), (38.4, -104.0), (250.0, -93.0), (500.0, -86.0)];
+
+const PA_TABLES_CC2510: &[PaCharacterization] = &[
+    PaCharacterization { min_mhz: 2400.0, max_mhz: 2483.5, settings: PA_SETTINGS_CC2510 },
+];
+
+const PA_TABLES_CC2500: &[PaCharacterization] = &[
+    PaCharacterization { min_mhz: 2400.0, max_mhz: 2483.5, settings: PA_SETTINGS_CC2500 },
+];
+
+// the 315 MHz band uses the 433 MHz column, 915 MHz the 868 MHz column
+const PA_TABLES_CC1110: &[PaCharacterization] = &[
+    PaCharacterization { min_mhz: 300.0, max_mhz: 464.0, settings: PA_SETTINGS_CC1110_433 },
+    PaCharacterization { min_mhz: 782.0, max_mhz: 928.0, settings: PA_SETTINGS_CC1110_868 },
+];
+
+pub static CC2510: ChipProfile = ChipProfile {
+    name: "CC2510",
+    partnum: 0x81,
+    default_crystal_hz: 26_000_000,
+    bands: BAND_2400,
+    pa_tables: PA_TABLES_CC2510,
+    register_address: SOC_REGISTER_ADDRESS,
+    status_address: SOC_STATUS_ADDRESS,
+    rssi_offset: 71.0,
+    sensitivity: SENSITIVITY_CC2510,
+    has_usb: false,
+    supports_ask_ook: false,
+};
+
+pub static CC2511: ChipProfile = ChipProfile {
+    name: "CC2511",
+    partnum: 0x91,
+    default_crystal_hz: 24_000_000,
+    bands: BAND_2400,
+    pa_tables: PA_TABLES_CC2510,
+    register_address: SOC_REGISTER_ADDRESS,
+    status_address: SOC_STATUS_ADDRESS,
+    rssi_offset: 71.0,
+    sensitivity: SENSITIVITY_CC2510,
+    has_usb: true,
+    supports_ask_ook: false,
+};
+
+pub static CC2500: ChipProfile = ChipProfile {
+    name: "CC2500",
+    partnum: 0x80,
+    default_crystal_hz: 26_000_000,
+    bands: BAND_2400,
+    pa_tables: PA_TABLES_CC2500,
+    register_address: SPI_REGISTER_ADDRESS,
+    status_address: SPI_STATUS_ADDRESS,
+    rssi_offset: 72.0,
+    sensitivity: SENSITIVITY_CC2500,
+    has_usb: false,
+    supports_ask_ook: true,
+};
+
+pub static CC1110: ChipProfile = ChipProfile {
+    name: "CC1110",
+    partnum: 0x01,
+    default_crystal_hz: 26_000_000,
+    bands: BANDS_SUB_GHZ,
+    pa_tables: PA_TABLES_CC1110,
+    register_address: SOC_REGISTER_ADDRESS,
+    status_address: SOC_STATUS_ADDRESS,
+    rssi_offset: 73.0,
+    sensitivity: SENSITIVITY_CC1110,
+    has_usb: false,
+    supports_ask_ook: true,
+};
+
+pub static CC1111: ChipProfile = ChipProfile {
+    name: "CC1111",
+    partnum: 0x11,
+    default_crystal_hz: 24_000_000,
+    bands: BANDS_SUB_GHZ,
+    pa_tables: PA_TABLES_CC1110,
+    register_address: SOC_REGISTER_ADDRESS,
+    status_address: SOC_STATUS_ADDRESS,
+    rssi_offset: 73.0,
+    sensitivity: SENSITIVITY_CC1110,
+    has_usb: true,
+    supports_ask_ook: true,
+};
+
+pub static ALL_CHIPS: [&ChipProfile; 5] = [&CC2510, &CC2511, &CC2500, &CC1110, &CC1111];
+
+fn range_distance(table: &PaCharacterization, frequency_mhz: f64) -> f64 {
+    if frequency_mhz < table.min_mhz {
+        table.min_mhz - frequency_mhz
+    } else if frequency_mhz > table.max_mhz {
+        frequency_mhz - table.max_mhz
+    } else {
+        0.0
+    }
+}
+
+pub fn from_partnum(partnum: u8) -> Option<&'static ChipProfile> {
+    ALL_CHIPS.into_iter().find(|chip| chip.partnum == partnum)
+}
+
+// Same as from_partnum, but only chips that have their PARTNUM register at `address`, the reply read
+// from the other chips' address map is meaningless
+pub fn from_partnum_at(address: u16, partnum: u8) -> Option<&'static ChipProfile> {
+    ALL_CHIPS.into_iter().find(|chip| chip.status_address.partnum == address && chip.partnum == partnum)
+}
+
+// Every address PARTNUM can be at (one per address map), in the order of ALL_CHIPS
+pub fn partnum_addresses() -> Vec<u16> {
+    let mut addresses: Vec<u16> = Vec::new();
+    for chip in ALL_CHIPS {
+        if !addresses.contains(&chip.status_address.partnum) {
+            addresses.push(chip.status_address.partnum);
+        }
+    }
+    addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partnum_is_matched_in_its_own_address_map() {
+        assert_eq!(partnum_addresses(), vec![0xDF36, 0x30]);
+        assert!(from_partnum_at(0x30, 0x80).is_some_and(|chip| std::ptr::eq(chip, &CC2500)));
+        assert!(from_partnum_at(0xDF36, 0x81).is_some_and(|chip| std::ptr::eq(chip, &CC2510)));
+        // a CC2500's PARTNUM read from the SoC map is not a CC2500
+        assert!(from_partnum_at(0xDF36, 0x80).is_none());
+        assert!(from_partnum_at(0x30, 0x81).is_none());
+    }
+
+    #[test]
+    fn spi_parts_only_read_pa_table0() {
+        let spi_registers = CC2500.readable_registers();
+        assert!(spi_registers.contains(&("PA_TABLE0", 0x3E)));
+        assert!(!spi_registers.iter().any(|(name, _)| *name == "PA_TABLE1"));
+        assert_eq!(CC2510.readable_registers().len(), CC2510.register_address.fields().len());
+    }
+
+    #[test]
+    fn pa_lookup_only_knows_characterized_bytes() {
+        let setting = CC2510.pa_setting_for_value(0xFE, 2440.0).unwrap();
+        assert_eq!((setting.dbm, setting.current_ma), (0, 26.0));
+        assert_eq!(CC2510.pa_value_for_dbm(-12, 2440.0), Some(0x95));
+        assert_eq!(CC2510.pa_value_for_dbm(-13, 2440.0), None);
+        assert!(CC2510.pa_setting_for_value(0x55, 2440.0).is_none());
+    }
+
+    #[test]
+    fn other_pa_bytes_are_interpolated() {
+        let listed = CC2510.estimate_pa_setting(0xFE, 2440.0);
+        assert!(listed.characterized);
+        assert_eq!((listed.dbm, listed.current_ma), (0.0, 26.0));
+        // 0x55 lies between 0x54 (-26 dBm) and 0x59 (-14 dBm), a fifth of the way along
+        let between = CC2510.estimate_pa_setting(0x55, 2440.0);
+        assert!(!between.characterized);
+        assert!((between.dbm - -23.6).abs() < 1e-9, "{}", between.dbm);
+        assert_eq!(between.text(), format!("≈ -23.6 dBm, {:.1} mA (estimate)", between.current_ma));
+        // past the highest listed byte (0xC8, 7 dBm) the table isn't extrapolated
+        let past_end = CC1110.estimate_pa_setting(0xFF, 433.92);
+        assert_eq!((past_end.dbm, past_end.current_ma, past_end.characterized), (7.0, 25.0, false));
+    }
+
+    #[test]
+    fn pa_table_follows_the_frequency() {
+        assert_eq!(CC1110.pa_value_for_dbm(10, 433.92), Some(0xC0));
+        assert_eq!(CC1110.pa_value_for_dbm(10, 868.3), Some(0xC2));
+        // 315 MHz is inside the 300-464 MHz range of the 433 MHz table
+        assert_eq!(CC1110.pa_value_for_dbm(10, 315.0), Some(0xC0));
+        assert_eq!(CC1110.pa_dbm_range(868.3), (-55, 10));
+        assert_eq!(CC2510.pa_dbm_range(2440.0), (-55, 1));
+    }
+
+    #[test]
+    fn slider_snaps_to_the_nearest_setting() {
+        assert_eq!(CC2510.nearest_pa_setting(0.4, 2440.0).value, 0xFE);
+        assert_eq!(CC2510.nearest_pa_setting(-11.2, 2440.0).dbm, -12);
+        assert_eq!(CC2510.nearest_pa_setting(-40.0, 2440.0).dbm, -30);
+        assert_eq!(CC2510.nearest_pa_setting(-50.0, 2440.0).dbm, -55);
+        assert_eq!(CC2510.nearest_pa_setting(20.0, 2440.0).dbm, 1);
+        assert_eq!(CC1110.nearest_pa_setting(5.8, 433.92).dbm, 5);
+    }
+}
@@ -0,0 +1,136 @@
+use std::error::Error;
+use std::fs;
+use crate::structs::RegisterValue;
+
+// A saved configuration, the register values plus the settings needed to decode them
+#[derive(Clone)]
+pub struct Configuration {
+    pub chip_name: String,
+    pub crystal_hz: u32,
+    pub registers: RegisterValue,
+}
+
+// Plain text with one `NAME = value` per line, so files can be read and compared by hand
+pub fn to_text(config: &Configuration) -> String {
+    let mut text = String::from("# Eos configuration\n");
+    text.push_str(&format!("CHIP = {}\n", config.chip_name));
+    text.push_str(&format!("CRYSTAL_HZ = {}\n", config.crystal_hz));
+    for (name, value) in config.registers.fields() {
+        text.push_str(&format!("{} = 0x{:02X}\n", name, value));
+    }
+    text
+}
+
+// Registers missing from the text keep the value they have in `base`
+// Same range the crystal field in the editor accepts, a zero crystal would divide by zero in the conversions
+pub const CRYSTAL_HZ_RANGE: std::ops::RangeInclusive<u32> = 1_000_000..=48_000_000;
+
+pub fn from_text(text: &str, base: RegisterValue) -> Result<Configuration, Box<dyn Error>> {
+    let mut chip_name = String::new();
+    let mut crystal_hz = None;
+    let mut registers = base;
+
+    for (line_number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("Line {}: expected NAME = value", line_number + 1))?;
+        let (key, value) = (key.trim(), value.trim());
+
+        match key {
+            "CHIP" => chip_name = value.to_string(),
+            "CRYSTAL_HZ" => {
+                let hz = value.parse::<u32>()?;
+                if !CRYSTAL_HZ_RANGE.contains(&hz) {
+                    return Err(format!("Line {}: CRYSTAL_HZ must be between 1 and 48 MHz", line_number + 1).into());
+                }
+                crystal_hz = Some(hz);
+            }
+            _ => {
+                let register = registers
+                    .field_mut(key)
+                    .ok_or(format!("Line {}: unknown register {}", line_number + 1, key))?;
+                *register = parse_byte(value)?;
+            }
+        }
+    }
+
+    Ok(Configuration {
+        chip_name,
+        crystal_hz: crystal_hz.ok_or("Missing CRYSTAL_HZ")?,
+        registers,
+    })
+}
+
+pub fn save(path: &str, config: &Configuration) -> Result<(), Box<dyn Error>> {
+    fs::write(path, to_text(config))?;
+    Ok(())
+}
+
+pub fn load(path: &str, base: RegisterValue) -> Result<Configuration, Box<dyn Error>> {
+    from_text(&fs::read_to_string(path)?, base)
+}
+
+fn parse_byte(value: &str) -> Result<u8, Box<dyn Error>> {
+    match value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+        Some(hex) => Ok(u8::from_str_radix(hex, 16)?),
+        None => Ok(value.parse::<u8>()?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configuration() -> Configuration {
+        Configuration {
+            chip_name: "CC2510".to_string(),
+            crystal_hz: 26_000_000,
+            registers: RegisterValue { freq2: 0x5E, freq1: 0xC4, freq0: 0xEC, pa_table7: 0xFF, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn text_round_trips() {
+        let config = configuration();
+        let text = to_text(&config);
+        assert!(text.contains("FREQ2 = 0x5E\n"));
+        let parsed = from_text(&text, RegisterValue::default()).unwrap();
+        assert_eq!(parsed.chip_name, config.chip_name);
+        assert_eq!(parsed.crystal_hz, config.crystal_hz);
+        assert_eq!(parsed.registers, config.registers);
+    }
+
+    #[test]
+    fn comments_decimal_values_and_missing_registers() {
+        let base = RegisterValue { sync1: 0xD3, ..Default::default() };
+        let text = "# comment\n\n  CRYSTAL_HZ = 24000000  \nCHANNR = 10\nADDR = 0Xab\n";
+        let parsed = from_text(text, base).unwrap();
+        assert_eq!(parsed.chip_name, "");
+        assert_eq!(parsed.crystal_hz, 24_000_000);
+        assert_eq!(parsed.registers.channr, 10);
+        assert_eq!(parsed.registers.addr, 0xAB);
+        // registers that aren't in the text keep their base value
+        assert_eq!(parsed.registers.sync1, 0xD3);
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let base = RegisterValue::default();
+        assert!(from_text("CRYSTAL_HZ = 26000000\nFREQ2 = 0xZZ\n", base).is_err());
+        assert!(from_text("CRYSTAL_HZ = 26000000\nFREQ2 = 256\n", base).is_err());
+        let unknown = from_text("CRYSTAL_HZ = 26000000\nFREQ3 = 0x00\n", base).err().unwrap();
+        assert_eq!(unknown.to_string(), "Line 2: unknown register FREQ3");
+        let missing_equals = from_text("CRYSTAL_HZ 26000000\n", base).err().unwrap();
+        assert_eq!(missing_equals.to_string(), "Line 1: expected NAME = value");
+        let missing_crystal = from_text("CHIP = CC2510\nFREQ2 = 0x5E\n", base).err().unwrap();
+        assert_eq!(missing_crystal.to_string(), "Missing CRYSTAL_HZ");
+        assert!(from_text("CRYSTAL_HZ = 26 MHz\n", base).is_err());
+        let zero_crystal = from_text("CRYSTAL_HZ = 0\n", base).err().unwrap();
+        assert_eq!(zero_crystal.to_string(), "Line 1: CRYSTAL_HZ must be between 1 and 48 MHz");
+        assert!(from_text("CRYSTAL_HZ = 48000001\n", base).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+// Register <-> engineering unit conversions from the CC2510 datasheet.
+// Every function takes the crystal (reference) frequency in Hz, nothing assumes 26 MHz.
+//
+// Inputs are turned into integer millihertz (or millibaud) once, everything after that is integer
+// math on the datasheet formulas. Every register value decodes to numerator / 2^n Hz, which is
+// exact in an f64, so encoding a decoded value always gives the same register back.
+
+const MHZ: u64 = 1_000_000;
+const KHZ: u64 = 1_000;
+
+// `value` in units of `unit` Hz as integer millihertz, NaN and negative values become 0
+fn to_millihertz(value: f64, unit: u64) -> u128 {
+    if value.is_nan() || value <= 0.0 {
+        return 0;
+    }
+    // `as` saturates on overflow
+    (value * unit as f64 * 1000.0).round() as u128
+}
+
+fn to_signed_millihertz(value: f64, unit: u64) -> i128 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value * unit as f64 * 1000.0).round() as i128
+}
+
+// numerator / denominator rounded to the nearest integer, halves away from zero
+fn divide_rounded(numerator: i128, denominator: i128) -> i128 {
+    let half = denominator / 2;
+    if numerator >= 0 {
+        (numerator + half) / denominator
+    } else {
+        (numerator - half) / denominator
+    }
+}
+
+// numerator / 2^shift Hz in `unit` Hz
+fn from_hertz_fraction(numerator: u64, shift: u32, unit: u64) -> f64 {
+    numerator as f64 / 2_u64.pow(shift) as f64 / unit as f64
+}
+
+// Of every (exponent, mantissa) pair, the one whose value numerator(e, m) / 2^shift Hz is closest to
+// `target_millihertz`. The errors are compared as integers, ties keep the smaller exponent, which has the finer step
+fn nearest_exponent_mantissa(target_millihertz: u128, shift: u32, max_exponent: u8, max_mantissa: u8, numerator: impl Fn(u8, u8) -> u64) -> (u8, u8) {
+    let target = target_millihertz.saturating_mul(1 << shift);
+    let mut best = (0, 0);
+    let mut best_error = u128::MAX;
+    for exponent in 0..=max_exponent {
+        for mantissa in 0..=max_mantissa {
+            let error = (numerator(exponent, mantissa) as u128 * 1000).abs_diff(target);
+            if error < best_error {
+                best = (exponent, mantissa);
+                best_error = error;
+            }
+        }
+    }
+    best
+}
+
+// f_carrier = f_xosc / 2^16 * FREQ, returns the 24 bit FREQ2:FREQ1:FREQ0 word closest to the
+// requested frequency, clamped to what fits in 24 bits
+pub fn frequency_to_register(frequency_mhz: f64, crystal_hz: u32) -> u32 {
+    let target = to_millihertz(frequency_mhz, MHZ).min(i128::MAX as u128 >> 17) as i128;
+    divide_rounded(target << 16, crystal_hz as i128 * 1000).clamp(0, 0xFF_FFFF) as u32
+}
+
+pub fn register_to_frequency(freq: u32, crystal_hz: u32) -> f64 {
+    from_hertz_fraction((freq & 0xFF_FFFF) as u64 * crystal_hz as u64, 16, MHZ)
+}
+
+// f_dev = f_xosc / 2^17 * (8 + DEVIATION_M) * 2^DEVIATION_E, returns the closest (exponent, mantissa)
+pub fn deviation_to_register(deviation_khz: f64, crystal_hz: u32) -> (u8, u8) {
+    nearest_exponent_mantissa(to_millihertz(deviation_khz, KHZ), 17, 7, 7, |deviation_e, deviation_m| deviation_numerator(deviation_e, deviation_m, crystal_hz))
+}
+
+fn deviation_numerator(deviation_e: u8, deviation_m: u8, crystal_hz: u32) -> u64 {
+    crystal_hz as u64 * ((8 + (deviation_m & 0x07) as u64) << (deviation_e & 0x07))
+}
+
+pub fn register_to_deviation(deviation_e: u8, deviation_m: u8, crystal_hz: u32) -> f64 {
+    from_hertz_fraction(deviation_numerator(deviation_e, deviation_m, crystal_hz), 17, KHZ)
+}
+
+// R_data = (256 + DRATE_M) * 2^DRATE_E / 2^28 * f_xosc, returns the closest (exponent, mantissa)
+pub fn data_rate_to_register(data_rate_kbaud: f64, crystal_hz: u32) -> (u8, u8) {
+    nearest_exponent_mantissa(to_millihertz(data_rate_kbaud, KHZ), 28, 15, 255, |dr_e, dr_m| data_rate_numerator(dr_e, dr_m, crystal_hz))
+}
+
+fn data_rate_numerator(dr_e: u8, dr_m: u8, crystal_hz: u32) -> u64 {
+    crystal_hz as u64 * ((256 + dr_m as u64) << (dr_e & 0x0F))
+}
+
+pub fn register_to_data_rate(dr_e: u8, dr_m: u8, crystal_hz: u32) -> f64 {
+    from_hertz_fraction(data_rate_numerator(dr_e, dr_m, crystal_hz), 28, KHZ)
+}
+
+// Difference between what was asked for and what the registers encode, as (absolute, ppm).
+// The ppm error is 0 for a zero target
+pub fn conversion_error(target: f64, achieved: f64) -> (f64, f64) {
+    let error = achieved - target;
+    let ppm = if target == 0.0 { 0.0 } else { error / target * 1_000_000.0 };
+    (error, ppm)
+}
+
+// BW_channel = f_xosc / (8 * (4 + CHANBW_M) * 2^CHANBW_E)
+pub fn register_to_channel_bandwidth(chanbw_e: u8, chanbw_m: u8, crystal_hz: u32) -> f64 {
+    crystal_hz as f64 / ((8 * (4 + (chanbw_m & 0x03) as u64)) << (chanbw_e & 0x03)) as f64 / KHZ as f64
+}
+
+// All 16 CHANBW_E/CHANBW_M settings as (exponent, mantissa, kHz), widest first
+pub fn channel_bandwidth_options(crystal_hz: u32) -> Vec<(u8, u8, f64)> {
+    let mut options = Vec::new();
+    for chanbw_e in 0..4 {
+        for chanbw_m in 0..4 {
+            options.push((chanbw_e, chanbw_m, register_to_channel_bandwidth(chanbw_e, chanbw_m, crystal_hz)));
+        }
+    }
+    options
+}
+
+// Carson's rule, the bandwidth a 2-FSK/GFSK/MSK signal occupies
+pub fn signal_bandwidth(data_rate_kbaud: f64, deviation_khz: f64) -> f64 {
+    data_rate_kbaud + 2.0 * deviation_khz
+}
+
+// Δf_channel = f_xosc / 2^18 * (256 + CHANSPC_M) * 2^CHANSPC_E
+fn channel_spacing_numerator(chanspc_e: u8, chanspc_m: u8, crystal_hz: u32) -> u64 {
+    crystal_hz as u64 * ((256 + chanspc_m as u64) << (chanspc_e & 0x03))
+}
+
+pub fn register_to_channel_spacing(chanspc_e: u8, chanspc_m: u8, crystal_hz: u32) -> f64 {
+    from_hertz_fraction(channel_spacing_numerator(chanspc_e, chanspc_m, crystal_hz), 18, KHZ)
+}
+
+// Returns the closest (exponent, mantissa) the 2 bit exponent and 8 bit mantissa can hold
+pub fn channel_spacing_to_register(spacing_khz: f64, crystal_hz: u32) -> (u8, u8) {
+    nearest_exponent_mantissa(to_millihertz(spacing_khz, KHZ), 18, 3, 255, |chanspc_e, chanspc_m| channel_spacing_numerator(chanspc_e, chanspc_m, crystal_hz))
+}
+
+// f_channel = f_base + CHANNR * Δf_channel, in MHz
+pub fn channel_frequency(base_frequency_mhz: f64, channel: u8, spacing_khz: f64) -> f64 {
+    base_frequency_mhz + channel as f64 * spacing_khz / 1000.0
+}
+
+// The same as channel_frequency, but summed over the common 2^18 denominator so it is exact
+pub fn register_to_channel_frequency(freq: u32, channr: u8, chanspc_e: u8, chanspc_m: u8, crystal_hz: u32) -> f64 {
+    let base = ((freq & 0xFF_FFFF) as u64 * crystal_hz as u64) << 2;
+    from_hertz_fraction(base + channr as u64 * channel_spacing_numerator(chanspc_e, chanspc_m, crystal_hz), 18, MHZ)
+}
+
+// f_IF = f_xosc / 2^10 * FREQ_IF, FREQ_IF is 5 bits
+pub fn if_frequency_to_register(if_frequency_khz: f64, crystal_hz: u32) -> u8 {
+    let target = to_millihertz(if_frequency_khz, KHZ).min(i128::MAX as u128 >> 11) as i128;
+    divide_rounded(target << 10, crystal_hz as i128 * 1000).clamp(0, 31) as u8
+}
+
+pub fn register_to_if_frequency(freq_if: u8, crystal_hz: u32) -> f64 {
+    from_hertz_fraction(crystal_hz as u64 * (freq_if & 0x1F) as u64, 10, KHZ)
+}
+
+// f_offset = f_xosc / 2^14 * FREQOFF, FREQOFF is an 8 bit two's complement value
+pub fn frequency_offset_to_register(offset_khz: f64, crystal_hz: u32) -> u8 {
+    let target = to_signed_millihertz(offset_khz, KHZ).clamp(i128::MIN >> 15, i128::MAX >> 15);
+    divide_rounded(target << 14, crystal_hz as i128 * 1000).clamp(-128, 127) as i8 as u8
+}
+
+pub fn register_to_frequency_offset(freqoff: u8, crystal_hz: u32) -> f64 {
+    (crystal_hz as i64 * (freqoff as i8) as i64) as f64 / 2_u64.pow(14) as f64 / KHZ as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_close, XOSC_24_MHZ, XOSC_26_MHZ};
+
+    // The CC2510 datasheet's register defaults decoded with a 26 MHz crystal
+    #[test]
+    fn datasheet_defaults_decode() {
+        assert_close(register_to_frequency(0x5E_C4EC, XOSC_26_MHZ), 2464.0, 0.000_5);
+        assert_close(register_to_deviation(4, 7, XOSC_26_MHZ), 47.607, 0.000_5);
+        assert_close(register_to_data_rate(12, 0x22, XOSC_26_MHZ), 115.051, 0.000_5);
+        assert_close(register_to_channel_bandwidth(2, 0, XOSC_26_MHZ), 203.125, 0.0);
+        assert_close(register_to_channel_spacing(2, 0xF8, XOSC_26_MHZ), 199.951, 0.000_5);
+        assert_close(register_to_if_frequency(0x0F, XOSC_26_MHZ), 380.859, 0.000_5);
+    }
+
+    #[test]
+    fn datasheet_examples_encode() {
+        assert_eq!(frequency_to_register(2464.0, XOSC_26_MHZ), 0x5E_C4EC);
+        assert_eq!(frequency_to_register(2433.0, XOSC_26_MHZ), 0x5D_93B1);
+        assert_eq!(deviation_to_register(47.607, XOSC_26_MHZ), (4, 7));
+        assert_eq!(data_rate_to_register(115.051, XOSC_26_MHZ), (12, 0x22));
+        // 2.4, 250 and 500 kBaud from the data rate table
+        assert_eq!(data_rate_to_register(2.4, XOSC_26_MHZ), (6, 131));
+        assert_eq!(data_rate_to_register(250.0, XOSC_26_MHZ), (13, 59));
+        assert_eq!(data_rate_to_register(500.0, XOSC_26_MHZ), (14, 59));
+        assert_eq!(channel_spacing_to_register(199.951, XOSC_26_MHZ), (2, 0xF8));
+        assert_eq!(if_frequency_to_register(380.859, XOSC_26_MHZ), 0x0F);
+    }
+
+    #[test]
+    fn frequency_offset_is_twos_complement() {
+        assert_eq!(frequency_offset_to_register(-1.587, XOSC_26_MHZ), 0xFF);
+        assert_eq!(frequency_offset_to_register(1.587, XOSC_26_MHZ), 0x01);
+        assert_close(register_to_frequency_offset(0x80, XOSC_26_MHZ), -203.125, 0.0);
+        assert_eq!(frequency_offset_to_register(-1000.0, XOSC_26_MHZ), 0x80);
+        assert_eq!(frequency_offset_to_register(1000.0, XOSC_26_MHZ), 0x7F);
+    }
+
+    #[test]
+    fn channel_frequency_adds_the_spacing() {
+        let base = register_to_frequency(0x5E_C4EC, XOSC_26_MHZ);
+        let spacing = register_to_channel_spacing(2, 0xF8, XOSC_26_MHZ);
+        assert_close(register_to_channel_frequency(0x5E_C4EC, 10, 2, 0xF8, XOSC_26_MHZ), channel_frequency(base, 10, spacing), 0.000_000_1);
+    }
+
+    #[test]
+    fn encode_decode_encode_round_trips() {
+        for crystal_hz in [XOSC_24_MHZ, XOSC_26_MHZ, 27_000_000] {
+            for freq in (0..=0xFF_FFFF).step_by(4099) {
+                assert_eq!(frequency_to_register(register_to_frequency(freq, crystal_hz), crystal_hz), freq);
+            }
+            for deviation_e in 0..8 {
+                for deviation_m in 0..8 {
+                    let deviation = register_to_deviation(deviation_e, deviation_m, crystal_hz);
+                    assert_eq!(deviation_to_register(deviation, crystal_hz), (deviation_e, deviation_m));
+                }
+            }
+            for dr_e in 0..16 {
+                for dr_m in 0..=255 {
+                    let data_rate = register_to_data_rate(dr_e, dr_m, crystal_hz);
+                    assert_eq!(data_rate_to_register(data_rate, crystal_hz), (dr_e, dr_m));
+                }
+            }
+            for chanspc_e in 0..4 {
+                for chanspc_m in 0..=255 {
+                    let spacing = register_to_channel_spacing(chanspc_e, chanspc_m, crystal_hz);
+                    assert_eq!(channel_spacing_to_register(spacing, crystal_hz), (chanspc_e, chanspc_m));
+                }
+            }
+            for freq_if in 0..32 {
+                assert_eq!(if_frequency_to_register(register_to_if_frequency(freq_if, crystal_hz), crystal_hz), freq_if);
+            }
+            for freqoff in 0..=255 {
+                assert_eq!(frequency_offset_to_register(register_to_frequency_offset(freqoff, crystal_hz), crystal_hz), freqoff);
+            }
+        }
+    }
+
+    #[test]
+    fn edge_values_saturate() {
+        assert_eq!(frequency_to_register(f64::NAN, XOSC_26_MHZ), 0);
+        assert_eq!(frequency_to_register(-5.0, XOSC_26_MHZ), 0);
+        assert_eq!(frequency_to_register(f64::INFINITY, XOSC_26_MHZ), 0xFF_FFFF);
+        assert_eq!(deviation_to_register(f64::INFINITY, XOSC_26_MHZ), (7, 7));
+        assert_eq!(deviation_to_register(0.0, XOSC_26_MHZ), (0, 0));
+        assert_eq!(data_rate_to_register(1e12, XOSC_26_MHZ), (15, 255));
+        assert_eq!(channel_spacing_to_register(f64::NAN, XOSC_26_MHZ), (0, 0));
+        assert_eq!(frequency_offset_to_register(f64::NEG_INFINITY, XOSC_26_MHZ), 0x80);
+    }
+}
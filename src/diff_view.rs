@@ -0,0 +1,358 @@
+use eframe::egui;
+use crate::{config_file, conversions, format_value, PendingRead, SerialApp};
+use crate::structs;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DiffSource {
+    Current,
+    File,
+    Radio,
+    // index into the editor's tabs
+    Tab(usize),
+}
+
+impl SerialApp {
+    fn diff_source_name(&self, source: DiffSource) -> String {
+        match source {
+            DiffSource::Current => "Current configuration".to_string(),
+            DiffSource::File => "File".to_string(),
+            DiffSource::Radio => "Live radio".to_string(),
+            DiffSource::Tab(index) => format!("Tab \"{}\"", self.tabs.tabs.get(index).map_or("", |tab| tab.name.as_str())),
+        }
+    }
+}
+
+pub struct DiffSide {
+    source: DiffSource,
+    path: String,
+    // the current configuration isn't copied here, it is always read straight from the editor
+    config: Option<config_file::Configuration>,
+    message: String,
+    // bumped whenever `config` is replaced. Radio replies still arrive after that (the reply queue
+    // can't drop them without misaligning the bytes behind them), so they are tagged with this and
+    // ignored once it has moved on
+    generation: u32,
+}
+
+impl DiffSide {
+    fn new(source: DiffSource, path: &str) -> Self {
+        Self { source, path: path.to_string(), config: None, message: String::new(), generation: 0 }
+    }
+
+    fn replace_config(&mut self, config: Option<config_file::Configuration>) {
+        self.config = config;
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+pub struct DiffState {
+    pub open: bool,
+    only_differences: bool,
+    sides: [DiffSide; 2],
+}
+
+impl DiffState {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            only_differences: true,
+            sides: [
+                DiffSide::new(DiffSource::File, "eos_config.txt"),
+                DiffSide::new(DiffSource::Current, ""),
+            ],
+        }
+    }
+}
+
+const SIDE_NAMES: [&str; 2] = ["Left", "Right"];
+
+impl SerialApp {
+    fn current_configuration(&self) -> config_file::Configuration {
+        config_file::Configuration {
+            chip_name: self.chip.name.to_string(),
+            crystal_hz: self.crystal_hz,
+            registers: self.register_value,
+        }
+    }
+
+    fn diff_side_configuration(&self, side: usize) -> Option<config_file::Configuration> {
+        match self.diff.sides[side].source {
+            DiffSource::Current => Some(self.current_configuration()),
+            DiffSource::Tab(index) => self.tab_configuration(index),
+            _ => self.diff.sides[side].config.clone(),
+        }
+    }
+
+    fn load_diff_side(&mut self, side: usize) {
+        match self.diff.sides[side].source {
+            DiffSource::Current | DiffSource::Tab(_) => {}
+            DiffSource::File => {
+                let result = config_file::load(&self.diff.sides[side].path, self.register_value);
+                let diff_side = &mut self.diff.sides[side];
+                match result {
+                    Ok(config) => {
+                        diff_side.replace_config(Some(config));
+                        diff_side.message = format!("Loaded {}", diff_side.path);
+                    }
+                    Err(e) => {
+                        diff_side.replace_config(None);
+                        diff_side.message = format!("Failed to load: {}", e);
+                    }
+                }
+            }
+            DiffSource::Radio => {
+                if self.port.is_none() {
+                    self.diff.sides[side].message = "No radio connected".to_string();
+                    return;
+                }
+                // registers fill in as the replies arrive
+                let current = self.current_configuration();
+                let diff_side = &mut self.diff.sides[side];
+                diff_side.replace_config(Some(current));
+                diff_side.message = "Reading...".to_string();
+                let generation = diff_side.generation;
+                for (name, address) in self.chip.readable_registers() {
+                    self.read_register(address, PendingRead::DiffRegister { side, generation, name });
+                }
+            }
+        }
+    }
+
+    fn save_diff_side(&mut self, side: usize) {
+        let diff_side = &mut self.diff.sides[side];
+        if let Some(config) = &diff_side.config {
+            diff_side.message = match config_file::save(&diff_side.path, config) {
+                Ok(()) => format!("Saved {}", diff_side.path),
+                Err(e) => format!("Failed to save: {}", e),
+            };
+        }
+    }
+
+    pub(crate) fn handle_diff_register_read(&mut self, side: usize, generation: u32, name: &'static str, value: u8) {
+        if self.diff.sides[side].generation != generation {
+            return;
+        }
+        if let Some(config) = &mut self.diff.sides[side].config {
+            if let Some(register) = config.registers.field_mut(name) {
+                *register = value;
+            }
+        }
+        let still_reading = self.pending_reads.iter().any(|pending| matches!(pending, PendingRead::DiffRegister { side: s, generation: g, .. } if *s == side && *g == generation));
+        if !still_reading {
+            // the registers that can't be read keep the current configuration's values
+            self.diff.sides[side].message = if self.chip.readable_registers().len() < self.chip.register_address.fields().len() {
+                "Read from radio (PA_TABLE1-7 not readable, copied from the current configuration)".to_string()
+            } else {
+                "Read from radio".to_string()
+            };
+        }
+    }
+
+    // Copies one register from side `from` into side `to`, the live radio side is read only
+    fn take_register(&mut self, from: usize, to: usize, name: &'static str) {
+        let Some(value) = self.diff_side_configuration(from).and_then(|config| config.registers.field(name)) else {
+            return;
+        };
+        let description = format!("{} 0x{:02X} → 0x{:02X} (from diff)", name, self.diff_side_configuration(to).and_then(|config| config.registers.field(name)).unwrap_or(0), value);
+        match self.diff.sides[to].source {
+            DiffSource::Tab(index) if index != self.tabs.active => self.set_tab_register(index, name, value, description),
+            DiffSource::Current | DiffSource::Tab(_) => {
+                let before = self.register_value;
+                if let Some(register) = self.register_value.field_mut(name) {
+                    *register = value;
+                }
+                self.record_change(before, description);
+                self.update_parameters_from_registers();
+            }
+            DiffSource::File => {
+                let diff_side = &mut self.diff.sides[to];
+                if let Some(register) = diff_side.config.as_mut().and_then(|config| config.registers.field_mut(name)) {
+                    *register = value;
+                    diff_side.message = "Unsaved changes".to_string();
+                }
+            }
+            DiffSource::Radio => {}
+        }
+    }
+
+    pub(crate) fn show_diff_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.diff.open;
+        egui::Window::new("Configuration Diff")
+            .open(&mut open)
+            .default_width(700.0)
+            .show(ctx, |ui| {
+                ui.columns(2, |columns| {
+                    for (side, ui) in columns.iter_mut().enumerate() {
+                        self.show_diff_side_controls(ui, side);
+                    }
+                });
+                ui.separator();
+
+                let (Some(left), Some(right)) = (self.diff_side_configuration(0), self.diff_side_configuration(1)) else {
+                    ui.label("Load both sides to compare them.");
+                    return;
+                };
+
+                ui.checkbox(&mut self.diff.only_differences, "Only show differences");
+                let mut take = None;
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("diff_registers")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Register");
+                            ui.strong(SIDE_NAMES[0]);
+                            ui.label("");
+                            ui.strong(SIDE_NAMES[1]);
+                            ui.strong("Changed fields");
+                            ui.end_row();
+
+                            for ((name, left_value), (_, right_value)) in left.registers.fields().into_iter().zip(right.registers.fields()) {
+                                let differs = left_value != right_value;
+                                if self.diff.only_differences && !differs {
+                                    continue;
+                                }
+                                ui.label(name);
+                                ui.label(bits_text(ui, left_value, right_value));
+                                ui.horizontal(|ui| {
+                                    let can_take_left = differs && self.diff.sides[1].source != DiffSource::Radio;
+                                    let can_take_right = differs && self.diff.sides[0].source != DiffSource::Radio;
+                                    if ui.add_enabled(can_take_left, egui::Button::new("→")).on_hover_text("Take left").clicked() {
+                                        take = Some((0, 1, name));
+                                    }
+                                    if ui.add_enabled(can_take_right, egui::Button::new("←")).on_hover_text("Take right").clicked() {
+                                        take = Some((1, 0, name));
+                                    }
+                                });
+                                ui.label(bits_text(ui, right_value, left_value));
+                                ui.label(structs::changed_bit_fields(name, left_value, right_value).join(", "));
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.separator();
+                    egui::Grid::new("diff_parameters")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Parameter");
+                            ui.strong(SIDE_NAMES[0]);
+                            ui.strong(SIDE_NAMES[1]);
+                            ui.end_row();
+
+                            for ((parameter, left_text), (_, right_text)) in decoded_parameters(&left).into_iter().zip(decoded_parameters(&right)) {
+                                let differs = left_text != right_text;
+                                if self.diff.only_differences && !differs {
+                                    continue;
+                                }
+                                ui.label(parameter);
+                                if differs {
+                                    ui.colored_label(ui.visuals().warn_fg_color, left_text);
+                                    ui.colored_label(ui.visuals().warn_fg_color, right_text);
+                                } else {
+                                    ui.label(left_text);
+                                    ui.label(right_text);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                if let Some((from, to, name)) = take {
+                    self.take_register(from, to, name);
+                }
+            });
+        self.diff.open = open;
+    }
+
+    // Closing a tab shifts the ones after it, a side showing the closed tab falls back to the current configuration
+    pub(crate) fn diff_tab_closed(&mut self, closed: usize) {
+        for diff_side in &mut self.diff.sides {
+            if let DiffSource::Tab(index) = diff_side.source {
+                if index == closed {
+                    diff_side.source = DiffSource::Current;
+                } else if index > closed {
+                    diff_side.source = DiffSource::Tab(index - 1);
+                }
+            }
+        }
+    }
+
+    fn show_diff_side_controls(&mut self, ui: &mut egui::Ui, side: usize) {
+        ui.strong(SIDE_NAMES[side]);
+        let mut sources = vec![DiffSource::Current, DiffSource::File, DiffSource::Radio];
+        sources.extend((0..self.tabs.tabs.len()).map(DiffSource::Tab));
+        let names: Vec<String> = sources.iter().map(|source| self.diff_source_name(*source)).collect();
+        let selected_name = self.diff_source_name(self.diff.sides[side].source);
+        let diff_side = &mut self.diff.sides[side];
+        let old_source = diff_side.source;
+        egui::ComboBox::from_id_salt(("diff_source", side))
+            .selected_text(selected_name)
+            .show_ui(ui, |ui| {
+                for (source, name) in sources.into_iter().zip(names) {
+                    ui.selectable_value(&mut diff_side.source, source, name);
+                }
+        });
+        if diff_side.source != old_source {
+            diff_side.replace_config(None);
+            diff_side.message.clear();
+        }
+
+        match diff_side.source {
+            DiffSource::Current | DiffSource::Tab(_) => {}
+            DiffSource::File => {
+                ui.add(egui::TextEdit::singleline(&mut diff_side.path).desired_width(200.0));
+                ui.horizontal(|ui| {
+                    if ui.button("Load").clicked() {
+                        self.load_diff_side(side);
+                    }
+                    if ui.add_enabled(self.diff.sides[side].config.is_some(), egui::Button::new("Save")).clicked() {
+                        self.save_diff_side(side);
+                    }
+                });
+            }
+            DiffSource::Radio => {
+                if ui.button("Read").clicked() {
+                    self.load_diff_side(side);
+                }
+            }
+        }
+        ui.label(&self.diff.sides[side].message);
+    }
+}
+
+// Binary value with the bits that differ from `other` highlighted
+fn bits_text(ui: &egui::Ui, value: u8, other: u8) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let font = egui::TextStyle::Monospace.resolve(ui.style());
+    for bit in (0..8).rev() {
+        let color = if (value ^ other) >> bit & 1 == 1 {
+            ui.visuals().warn_fg_color
+        } else {
+            ui.visuals().text_color()
+        };
+        job.append(&((value >> bit) & 1).to_string(), 0.0, egui::TextFormat::simple(font.clone(), color));
+    }
+    job.append(&format!("  0x{:02X}", value), 0.0, egui::TextFormat::simple(font, ui.visuals().text_color()));
+    job
+}
+
+fn decoded_parameters(config: &config_file::Configuration) -> Vec<(&'static str, String)> {
+    let registers = &config.registers;
+    let crystal_hz = config.crystal_hz;
+    vec![
+        ("Chip", config.chip_name.clone()),
+        ("Crystal", format!("{} kHz", crystal_hz / 1000)),
+        ("Base Frequency", format!("{} MHz", format_value(registers.base_frequency(crystal_hz)))),
+        ("Channel Number", registers.channr.to_string()),
+        ("Channel Frequency", format!("{} MHz", format_value(registers.channel_frequency(crystal_hz)))),
+        ("Modulation Scheme", registers.modulation_name().to_string()),
+        ("Data Rate", format!("{} kBaud", format_value(registers.data_rate(crystal_hz)))),
+        ("Deviation", format!("{} kHz", format_value(registers.deviation(crystal_hz)))),
+        ("Channel Bandwidth", format!("{} kHz", format_value(registers.channel_bandwidth(crystal_hz)))),
+        ("Channel Spacing", format!("{} kHz", format_value(registers.channel_spacing(crystal_hz)))),
+        ("IF Frequency", format!("{} kHz", format_value(conversions::register_to_if_frequency(registers.fsctrl1, crystal_hz)))),
+        ("Frequency Offset", format!("{} kHz", format_value(conversions::register_to_frequency_offset(registers.fsctrl0, crystal_hz)))),
+        ("Data Whitening", (registers.pktctrl0 & 0x40 != 0).to_string()),
+        ("Manchester Enable", (registers.mdmcfg2 & 0x08 != 0).to_string()),
+        ("PA_POWER", registers.pa_power().to_string()),
+        ("PA Table", registers.pa_table().iter().map(|value| format!("{:02X}", value)).collect::<Vec<String>>().join(" ")),
+    ]
+}
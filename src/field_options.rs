@@ -0,0 +1,204 @@
+// Named values of register bit fields, indexed by the field value, from the datasheet register tables
+
+// MDMCFG2.SYNC_MODE
+pub const SYNC_MODES: [&str; 8] = [
+    "No preamble/sync",
+    "15/16 sync word bits",
+    "16/16 sync word bits",
+    "30/32 sync word bits",
+    "No preamble/sync, carrier-sense above threshold",
+    "15/16 + carrier-sense above threshold",
+    "16/16 + carrier-sense above threshold",
+    "30/32 + carrier-sense above threshold",
+];
+
+// MDMCFG1.NUM_PREAMBLE, minimum number of preamble bytes transmitted
+pub const PREAMBLE_BYTES: [u8; 8] = [2, 3, 4, 6, 8, 12, 16, 24];
+
+// PKTCTRL0.LENGTH_CONFIG
+pub const LENGTH_CONFIGS: [&str; 4] = [
+    "Fixed (PKTLEN)",
+    "Variable (first byte after sync)",
+    "Infinite",
+    "Reserved",
+];
+
+// PKTCTRL1.ADR_CHK
+pub const ADDRESS_CHECK_MODES: [&str; 4] = [
+    "No address check",
+    "Address check, no broadcast",
+    "Address check, 0x00 broadcast",
+    "Address check, 0x00 and 0xFF broadcast",
+];
+
+// MCSM1.RXOFF_MODE, what happens after a packet has been received
+pub const RXOFF_MODES: [&str; 4] = ["IDLE", "FSTXON", "TX", "Stay in RX"];
+
+// MCSM1.TXOFF_MODE, what happens after a packet has been sent
+pub const TXOFF_MODES: [&str; 4] = ["IDLE", "FSTXON", "Stay in TX", "RX"];
+
+// MCSM1.CCA_MODE
+pub const CCA_MODES: [&str; 4] = [
+    "Always",
+    "If RSSI below threshold",
+    "Unless currently receiving a packet",
+    "If RSSI below threshold unless currently receiving a packet",
+];
+
+// MCSM0.FS_AUTOCAL
+pub const FS_AUTOCAL_MODES: [&str; 4] = [
+    "Never (manual calibration)",
+    "When going from IDLE to RX or TX",
+    "When going from RX or TX back to IDLE",
+    "Every 4th time going from RX or TX to IDLE",
+];
+
+// MCSM2.RX_TIME, the timeout is a fraction of the EVENT0 period
+pub const RX_TIMES: [&str; 8] = [
+    "EVENT0 / 2^3",
+    "EVENT0 / 2^4",
+    "EVENT0 / 2^5",
+    "EVENT0 / 2^6",
+    "EVENT0 / 2^7",
+    "EVENT0 / 2^8",
+    "EVENT0 / 2^9",
+    "No timeout",
+];
+
+// AGCCTRL2.MAX_DVGA_GAIN
+pub const MAX_DVGA_GAINS: [&str; 4] = [
+    "All gain settings",
+    "Highest setting not used",
+    "2 highest settings not used",
+    "3 highest settings not used",
+];
+
+// AGCCTRL2.MAX_LNA_GAIN, reduction from the maximum LNA gain
+pub const MAX_LNA_GAINS: [&str; 8] = [
+    "Maximum",
+    "2.6 dB below maximum",
+    "6.1 dB below maximum",
+    "7.4 dB below maximum",
+    "9.2 dB below maximum",
+    "11.5 dB below maximum",
+    "14.6 dB below maximum",
+    "17.1 dB below maximum",
+];
+
+// AGCCTRL2.MAGN_TARGET, target amplitude from the channel filter in dB
+pub const MAGN_TARGETS_DB: [u8; 8] = [24, 27, 30, 33, 36, 38, 40, 42];
+
+// AGCCTRL1.CARRIER_SENSE_REL_THR, RSSI increase in dB that asserts carrier sense
+pub const CARRIER_SENSE_REL_THRESHOLDS_DB: [u8; 4] = [0, 6, 10, 14];
+
+// AGCCTRL0.HYST_LEVEL
+pub const HYST_LEVELS: [&str; 4] = ["No hysteresis", "Low hysteresis", "Medium hysteresis", "Large hysteresis"];
+
+// AGCCTRL0.WAIT_TIME, samples to wait after a gain change
+pub const WAIT_TIMES: [u8; 4] = [8, 16, 24, 32];
+
+// AGCCTRL0.AGC_FREEZE
+pub const AGC_FREEZE_MODES: [&str; 4] = [
+    "Normal operation",
+    "Freeze when sync word is found",
+    "Freeze analog gain, digital gain still adjusts",
+    "Freeze analog and digital gain",
+];
+
+// AGCCTRL0.FILTER_LENGTH, channel filter samples averaged for the amplitude
+pub const FILTER_LENGTHS: [u8; 4] = [8, 16, 32, 64];
+
+// FOCCFG.FOC_PRE_K, frequency compensation loop gain before sync word
+pub const FOC_PRE_K: [&str; 4] = ["K", "2K", "3K", "4K"];
+
+// FOCCFG.FOC_POST_K, frequency compensation loop gain after sync word
+pub const FOC_POST_K: [&str; 2] = ["Same as before sync word", "K/2"];
+
+// FOCCFG.FOC_LIMIT, divisor of the channel bandwidth, 0 turns compensation off
+pub const FOC_LIMIT_DIVISORS: [u8; 4] = [0, 8, 4, 2];
+
+// BSCFG.BS_PRE_KI, bit synchronization integral gain before sync word
+pub const BS_PRE_KI: [&str; 4] = ["KI", "2KI", "3KI", "4KI"];
+
+// BSCFG.BS_PRE_KP, bit synchronization proportional gain before sync word
+pub const BS_PRE_KP: [&str; 4] = ["KP", "2KP", "3KP", "4KP"];
+
+// BSCFG.BS_POST_KI
+pub const BS_POST_KI: [&str; 2] = ["Same as before sync word", "KI/2"];
+
+// BSCFG.BS_POST_KP
+pub const BS_POST_KP: [&str; 2] = ["Same as before sync word", "KP"];
+
+// BSCFG.BS_LIMIT, data rate offset the bit synchronization can compensate in percent, 0 turns it off
+pub const BS_LIMITS_PERCENT: [f64; 4] = [0.0, 3.125, 6.25, 12.5];
+
+// IOCFGx.GDOx_CFG, the signal driven on a GDO pin
+pub const GDO_SIGNALS: [&str; 64] = [
+    "RX FIFO at or above threshold",
+    "RX FIFO at or above threshold or end of packet",
+    "TX FIFO at or above threshold",
+    "TX FIFO full",
+    "RX FIFO overflow",
+    "TX FIFO underflow",
+    "Sync word sent/received",
+    "Packet received with CRC OK",
+    "Preamble quality reached",
+    "Clear channel assessment",
+    "PLL lock",
+    "Serial clock",
+    "Serial synchronous data output",
+    "Serial data output",
+    "Carrier sense",
+    "CRC OK",
+    "Reserved (0x10)",
+    "Reserved (0x11)",
+    "Reserved (0x12)",
+    "Reserved (0x13)",
+    "Reserved (0x14)",
+    "Reserved (0x15)",
+    "RX_HARD_DATA[1]",
+    "RX_HARD_DATA[0]",
+    "Reserved (0x18)",
+    "Reserved (0x19)",
+    "Reserved (0x1A)",
+    "PA_PD",
+    "LNA_PD",
+    "RX_SYMBOL_TICK",
+    "Reserved (0x1E)",
+    "Reserved (0x1F)",
+    "Reserved (0x20)",
+    "Reserved (0x21)",
+    "Reserved (0x22)",
+    "Reserved (0x23)",
+    "WOR_EVNT0",
+    "WOR_EVNT1",
+    "Reserved (0x26)",
+    "CLK_32k",
+    "Reserved (0x28)",
+    "CHIP_RDYn",
+    "Reserved (0x2A)",
+    "XOSC_STABLE",
+    "Reserved (0x2C)",
+    "GDO0_Z_EN_N",
+    "High impedance",
+    "Low (0)",
+    "CLK_XOSC/1",
+    "CLK_XOSC/1.5",
+    "CLK_XOSC/2",
+    "CLK_XOSC/3",
+    "CLK_XOSC/4",
+    "CLK_XOSC/6",
+    "CLK_XOSC/8",
+    "CLK_XOSC/12",
+    "CLK_XOSC/16",
+    "CLK_XOSC/24",
+    "CLK_XOSC/32",
+    "CLK_XOSC/48",
+    "CLK_XOSC/64",
+    "CLK_XOSC/96",
+    "CLK_XOSC/128",
+    "CLK_XOSC/192",
+];
+
+// IOCFG1.GDO_DS
+pub const GDO_DRIVE_STRENGTHS: [&str; 2] = ["Low", "High"];
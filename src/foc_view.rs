@@ -0,0 +1,84 @@
+use eframe::egui;
+use crate::{field_options, format_value, SerialApp};
+
+pub struct FocView {
+    pub open: bool,
+}
+
+impl FocView {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+}
+
+impl SerialApp {
+    // The saturation point of the frequency offset compensation, ±kHz, 0 when it is off
+    fn foc_range(&self) -> f64 {
+        let divisor = field_options::FOC_LIMIT_DIVISORS[(self.register_value.foccfg & 0x03) as usize];
+        if divisor == 0 {
+            0.0
+        } else {
+            self.channel_bandwidth() / divisor as f64
+        }
+    }
+
+    // The data rate offset the bit synchronization can follow, ±kBaud
+    fn bs_range(&self) -> f64 {
+        self.dr() * field_options::BS_LIMITS_PERCENT[(self.register_value.bscfg & 0x03) as usize] / 100.0
+    }
+
+    pub(crate) fn show_foc_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.foc_view.open;
+        egui::Window::new("Offset Compensation")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let foc_limits: Vec<String> = field_options::FOC_LIMIT_DIVISORS.iter()
+                    .map(|divisor| if *divisor == 0 {"No compensation".to_string()} else {format!("±BW/{}", divisor)})
+                    .collect();
+                let bs_limits: Vec<String> = field_options::BS_LIMITS_PERCENT.iter()
+                    .map(|percent| if *percent == 0.0 {"No compensation".to_string()} else {format!("±{}%", percent)})
+                    .collect();
+
+                ui.strong("Frequency offset compensation");
+                egui::Grid::new("foccfg_fields")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.register_field_combo(ui, "FOCCFG", "Wait for carrier sense", &["Off", "On"], 5, 0x01);
+                        self.register_field_combo(ui, "FOCCFG", "Gain before sync word", &field_options::FOC_PRE_K, 3, 0x03);
+                        self.register_field_combo(ui, "FOCCFG", "Gain after sync word", &field_options::FOC_POST_K, 2, 0x01);
+                        self.register_field_combo(ui, "FOCCFG", "Limit", &foc_limits, 0, 0x03);
+                    });
+
+                let foc_range = self.foc_range();
+                if foc_range == 0.0 {
+                    ui.label("Compensation range: off");
+                } else {
+                    ui.label(format!("Compensation range: ±{} kHz of the {} kHz channel bandwidth", format_value(foc_range), format_value(self.channel_bandwidth())));
+                }
+                // the datasheet recommends at most ±BW/4, past that the offset can pull the signal out of the channel filter
+                if foc_range > self.channel_bandwidth() / 4.0 {
+                    ui.colored_label(ui.visuals().warn_fg_color, "Range exceeds the recommended ±BW/4");
+                }
+                ui.separator();
+
+                ui.strong("Bit synchronization");
+                egui::Grid::new("bscfg_fields")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.register_field_combo(ui, "BSCFG", "Integral gain before sync word", &field_options::BS_PRE_KI, 6, 0x03);
+                        self.register_field_combo(ui, "BSCFG", "Proportional gain before sync word", &field_options::BS_PRE_KP, 4, 0x03);
+                        self.register_field_combo(ui, "BSCFG", "Integral gain after sync word", &field_options::BS_POST_KI, 3, 0x01);
+                        self.register_field_combo(ui, "BSCFG", "Proportional gain after sync word", &field_options::BS_POST_KP, 2, 0x01);
+                        self.register_field_combo(ui, "BSCFG", "Limit", &bs_limits, 0, 0x03);
+                    });
+                let bs_range = self.bs_range();
+                if bs_range == 0.0 {
+                    ui.label("Data rate compensation: off");
+                } else {
+                    ui.label(format!("Data rate compensation: ±{} kBaud", format_value(bs_range)));
+                }
+            });
+        self.foc_view.open = open;
+    }
+}
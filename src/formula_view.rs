@@ -0,0 +1,146 @@
+use eframe::egui;
+use crate::{conversions, format_value, SerialApp};
+
+pub struct FormulaView {
+    pub open: bool,
+}
+
+impl FormulaView {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+}
+
+// One worked calculation: the datasheet formula, then (step, value) rows, then the registers it ends up in
+struct Breakdown {
+    title: &'static str,
+    formula: &'static str,
+    steps: Vec<(&'static str, String)>,
+    registers: Vec<(&'static str, u8)>,
+}
+
+fn show_breakdown(ui: &mut egui::Ui, breakdown: Breakdown) {
+    egui::CollapsingHeader::new(breakdown.title).show(ui, |ui| {
+        ui.monospace(breakdown.formula);
+        egui::Grid::new(("formula_steps", breakdown.title))
+            .striped(true)
+            .show(ui, |ui| {
+                for (step, value) in breakdown.steps {
+                    ui.label(step);
+                    ui.monospace(value);
+                    ui.end_row();
+                }
+                for (register, value) in breakdown.registers {
+                    ui.label(register);
+                    ui.monospace(format!("{:08b}  0x{:02X}", value, value));
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+impl SerialApp {
+    fn formula_breakdowns(&self) -> Vec<Breakdown> {
+        let registers = self.register_value;
+        let crystal_hz = self.crystal_hz;
+        let crystal = ("f_xosc", format!("{} Hz", crystal_hz));
+
+        let freq = registers.freq_word();
+        let frequency_step = crystal_hz as f64 / 2_u32.pow(16) as f64;
+
+        let deviation_e = (registers.deviatn & 0x70) >> 4;
+        let deviation_m = registers.deviatn & 0x07;
+        let deviation_step = crystal_hz as f64 / 2_u32.pow(17) as f64;
+
+        let dr_e = registers.mdmcfg4 & 0x0F;
+        let dr_m = registers.mdmcfg3;
+        let dr_step = crystal_hz as f64 / 2_u32.pow(28) as f64;
+
+        let chanbw_e = registers.mdmcfg4 >> 6;
+        let chanbw_m = (registers.mdmcfg4 & 0x30) >> 4;
+        let chanbw_divider = 8 * (4 + chanbw_m as u32) * 2_u32.pow(chanbw_e as u32);
+
+        let chanspc_e = registers.mdmcfg1 & 0x03;
+        let chanspc_m = registers.mdmcfg0;
+        let spacing_step = crystal_hz as f64 / 2_u32.pow(18) as f64;
+
+        vec![
+            Breakdown {
+                title: "Base frequency",
+                formula: "f_carrier = f_xosc / 2^16 · FREQ",
+                steps: vec![
+                    crystal.clone(),
+                    ("f_xosc / 2^16", format!("{} Hz per step", format_value(frequency_step))),
+                    ("FREQ", format!("{} (FREQ2:FREQ1:FREQ0)", freq)),
+                    ("f_carrier", format!("{} · {} = {} MHz", format_value(frequency_step), freq, format_value(conversions::register_to_frequency(freq, crystal_hz)))),
+                ],
+                registers: vec![("FREQ2", registers.freq2), ("FREQ1", registers.freq1), ("FREQ0", registers.freq0)],
+            },
+            Breakdown {
+                title: "Deviation",
+                formula: "f_dev = f_xosc / 2^17 · (8 + DEVIATION_M) · 2^DEVIATION_E",
+                steps: vec![
+                    crystal.clone(),
+                    ("f_xosc / 2^17", format!("{} Hz", format_value(deviation_step))),
+                    ("DEVIATION_E / DEVIATION_M", format!("{} / {}", deviation_e, deviation_m)),
+                    ("(8 + M) · 2^E", format!("{} · {} = {}", 8 + deviation_m as u32, 2_u32.pow(deviation_e as u32), (8 + deviation_m as u32) * 2_u32.pow(deviation_e as u32))),
+                    ("f_dev", format!("{} kHz", format_value(conversions::register_to_deviation(deviation_e, deviation_m, crystal_hz)))),
+                ],
+                registers: vec![("DEVIATN", registers.deviatn)],
+            },
+            Breakdown {
+                title: "Data rate",
+                formula: "R_data = (256 + DRATE_M) · 2^DRATE_E / 2^28 · f_xosc",
+                steps: vec![
+                    crystal.clone(),
+                    ("f_xosc / 2^28", format!("{} Baud", format_value(dr_step))),
+                    ("DRATE_E / DRATE_M", format!("{} / {}", dr_e, dr_m)),
+                    ("(256 + M) · 2^E", format!("{} · {} = {}", 256 + dr_m as u32, 2_u32.pow(dr_e as u32), (256 + dr_m as u32) * 2_u32.pow(dr_e as u32))),
+                    ("R_data", format!("{} kBaud", format_value(conversions::register_to_data_rate(dr_e, dr_m, crystal_hz)))),
+                ],
+                registers: vec![("MDMCFG4", registers.mdmcfg4), ("MDMCFG3", registers.mdmcfg3)],
+            },
+            Breakdown {
+                title: "Channel bandwidth",
+                formula: "BW_channel = f_xosc / (8 · (4 + CHANBW_M) · 2^CHANBW_E)",
+                steps: vec![
+                    crystal.clone(),
+                    ("CHANBW_E / CHANBW_M", format!("{} / {}", chanbw_e, chanbw_m)),
+                    ("8 · (4 + M) · 2^E", format!("8 · {} · {} = {}", 4 + chanbw_m as u32, 2_u32.pow(chanbw_e as u32), chanbw_divider)),
+                    ("BW_channel", format!("{} / {} = {} kHz", crystal_hz, chanbw_divider, format_value(conversions::register_to_channel_bandwidth(chanbw_e, chanbw_m, crystal_hz)))),
+                ],
+                registers: vec![("MDMCFG4", registers.mdmcfg4)],
+            },
+            Breakdown {
+                title: "Channel spacing",
+                formula: "Δf_channel = f_xosc / 2^18 · (256 + CHANSPC_M) · 2^CHANSPC_E",
+                steps: vec![
+                    crystal,
+                    ("f_xosc / 2^18", format!("{} Hz", format_value(spacing_step))),
+                    ("CHANSPC_E / CHANSPC_M", format!("{} / {}", chanspc_e, chanspc_m)),
+                    ("(256 + M) · 2^E", format!("{} · {} = {}", 256 + chanspc_m as u32, 2_u32.pow(chanspc_e as u32), (256 + chanspc_m as u32) * 2_u32.pow(chanspc_e as u32))),
+                    ("Δf_channel", format!("{} kHz", format_value(conversions::register_to_channel_spacing(chanspc_e, chanspc_m, crystal_hz)))),
+                    ("f_channel", format!("f_carrier + CHANNR · Δf_channel = {} MHz (channel {})", format_value(self.channel_frequency()), registers.channr)),
+                ],
+                registers: vec![("MDMCFG1", registers.mdmcfg1), ("MDMCFG0", registers.mdmcfg0), ("CHANNR", registers.channr)],
+            },
+        ]
+    }
+
+    pub(crate) fn show_formula_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.formula_view.open;
+        let breakdowns = self.formula_breakdowns();
+        egui::Window::new("Formulas")
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label("How each parameter follows from the registers, using the datasheet formulas.");
+                egui::ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for breakdown in breakdowns {
+                        show_breakdown(ui, breakdown);
+                    }
+                });
+            });
+        self.formula_view.open = open;
+    }
+}
@@ -0,0 +1,57 @@
+use eframe::egui;
+use crate::{field_options, SerialApp};
+
+// (pin, register) for each GDO output
+const GDO_PINS: [(&str, &str); 3] = [("GDO0", "IOCFG0"), ("GDO1", "IOCFG1"), ("GDO2", "IOCFG2")];
+
+// Presets for driving an external front end, PA_PD and LNA_PD are active low power down signals
+const FRONT_END_PRESETS: [(&str, u8, u8); 2] = [
+    ("PA enable on GDO0, LNA enable on GDO2", 0x5B, 0x5C),
+    ("PA_PD on GDO0, LNA_PD on GDO2", 0x1B, 0x1C),
+];
+
+pub struct GpioView {
+    pub open: bool,
+}
+
+impl GpioView {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+}
+
+impl SerialApp {
+    fn apply_front_end_preset(&mut self, name: &str, iocfg0: u8, iocfg2: u8) {
+        let before = self.register_value;
+        self.register_value.iocfg0 = (self.register_value.iocfg0 & 0x80) | iocfg0;
+        self.register_value.iocfg2 = (self.register_value.iocfg2 & 0x80) | iocfg2;
+        self.record_change(before, format!("GPIO preset \"{}\"", name));
+    }
+
+    pub(crate) fn show_gpio_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.gpio_view.open;
+        egui::Window::new("GPIO Outputs")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("iocfg_fields")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (pin, register) in GDO_PINS {
+                            self.register_field_combo(ui, register, &format!("{} signal", pin), &field_options::GDO_SIGNALS, 0, 0x3F);
+                            self.register_field_combo(ui, register, &format!("{} output", pin), &["Active high", "Active low (inverted)"], 6, 0x01);
+                        }
+                        self.register_field_combo(ui, "IOCFG1", "Drive strength", &field_options::GDO_DRIVE_STRENGTHS, 7, 0x01);
+                    });
+                ui.separator();
+
+                ui.label("External PA/LNA");
+                for (name, iocfg0, iocfg2) in FRONT_END_PRESETS {
+                    if ui.button(name).clicked() {
+                        self.apply_front_end_preset(name, iocfg0, iocfg2);
+                    }
+                }
+            });
+        self.gpio_view.open = open;
+    }
+}
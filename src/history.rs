@@ -0,0 +1,173 @@
+use crate::chips::ChipProfile;
+use crate::structs::RegisterValue;
+
+// Everything needed to decode a configuration, the chip and crystal are restored together with the
+// registers so undoing a load doesn't decode the old registers with the new crystal
+#[derive(Clone, Copy)]
+pub struct Snapshot {
+    pub registers: RegisterValue,
+    pub chip: &'static ChipProfile,
+    pub crystal_hz: u32,
+}
+
+impl PartialEq for Snapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.registers == other.registers && std::ptr::eq(self.chip, other.chip) && self.crystal_hz == other.crystal_hz
+    }
+}
+
+// A single step in the edit history, the configuration right after the described change
+pub struct HistoryEntry {
+    pub description: String,
+    pub snapshot: Snapshot,
+}
+
+// Linear undo/redo stack, entry 0 is always the starting configuration
+pub struct History {
+    entries: Vec<HistoryEntry>,
+    current: usize,
+}
+
+impl History {
+    pub fn new(initial: Snapshot) -> Self {
+        Self {
+            entries: vec![HistoryEntry {
+                description: "Initial configuration".to_string(),
+                snapshot: initial,
+            }],
+            current: 0,
+        }
+    }
+
+    // Adding a change after undoing throws away the entries that could have been redone
+    pub fn push(&mut self, description: String, snapshot: Snapshot) {
+        self.entries.truncate(self.current + 1);
+        self.entries.push(HistoryEntry { description, snapshot });
+        self.current = self.entries.len() - 1;
+    }
+
+    pub fn undo(&mut self) -> Option<Snapshot> {
+        if self.can_undo() {
+            self.jump_to(self.current - 1)
+        } else {
+            None
+        }
+    }
+
+    pub fn redo(&mut self) -> Option<Snapshot> {
+        if self.can_redo() {
+            self.jump_to(self.current + 1)
+        } else {
+            None
+        }
+    }
+
+    pub fn jump_to(&mut self, index: usize) -> Option<Snapshot> {
+        let entry = self.entries.get(index)?;
+        self.current = index;
+        Some(entry.snapshot)
+    }
+    pub fn can_undo(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.current + 1 < self.entries.len()
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips;
+    use crate::test_util::{XOSC_24_MHZ, XOSC_26_MHZ};
+
+    fn registers(channr: u8) -> RegisterValue {
+        RegisterValue { channr, ..Default::default() }
+    }
+
+    fn snapshot(channr: u8) -> Snapshot {
+        Snapshot { registers: registers(channr), chip: &chips::CC2510, crystal_hz: XOSC_26_MHZ }
+    }
+
+    // the registers an undo, redo or jump restored
+    fn restored(snapshot: Option<Snapshot>) -> Option<RegisterValue> {
+        snapshot.map(|snapshot| snapshot.registers)
+    }
+
+    fn history_with_three_changes() -> History {
+        let mut history = History::new(snapshot(0));
+        for channr in 1..=3 {
+            history.push(format!("Channel number {}", channr), snapshot(channr));
+        }
+        history
+    }
+
+    #[test]
+    fn new_history_has_only_the_initial_entry() {
+        let mut history = History::new(snapshot(0));
+        assert_eq!(history.entries().len(), 1);
+        assert_eq!(history.current(), 0);
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+        assert!(history.undo().is_none());
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_entries() {
+        let mut history = history_with_three_changes();
+        assert_eq!(history.current(), 3);
+        assert_eq!(restored(history.undo()), Some(registers(2)));
+        assert_eq!(restored(history.undo()), Some(registers(1)));
+        assert_eq!(restored(history.redo()), Some(registers(2)));
+        assert_eq!(history.current(), 2);
+        assert!(history.can_undo());
+        assert!(history.can_redo());
+        assert_eq!(restored(history.redo()), Some(registers(3)));
+        assert!(history.redo().is_none());
+        assert_eq!(history.current(), 3);
+    }
+
+    #[test]
+    fn jump_to_moves_without_dropping_entries() {
+        let mut history = history_with_three_changes();
+        assert_eq!(restored(history.jump_to(0)), Some(registers(0)));
+        assert_eq!(history.current(), 0);
+        assert_eq!(history.entries().len(), 4);
+        assert!(history.jump_to(4).is_none());
+        assert_eq!(history.current(), 0);
+        assert_eq!(restored(history.jump_to(2)), Some(registers(2)));
+    }
+
+    #[test]
+    fn push_after_undo_truncates_the_redo_branch() {
+        let mut history = history_with_three_changes();
+        history.undo();
+        history.undo();
+        history.push("Channel number 9".to_string(), snapshot(9));
+        assert_eq!(history.entries().len(), 3);
+        assert_eq!(history.current(), 2);
+        assert!(!history.can_redo());
+        assert_eq!(history.entries()[2].description, "Channel number 9");
+        assert_eq!(restored(history.undo()), Some(registers(1)));
+    }
+
+    #[test]
+    fn undo_restores_the_chip_and_crystal() {
+        let mut history = History::new(snapshot(0));
+        history.push("Loaded".to_string(), Snapshot { registers: registers(0), chip: &chips::CC1110, crystal_hz: XOSC_24_MHZ });
+        let undone = history.undo().unwrap();
+        assert!(std::ptr::eq(undone.chip, &chips::CC2510));
+        assert_eq!(undone.crystal_hz, XOSC_26_MHZ);
+        assert!(history.redo() != Some(snapshot(0)));
+    }
+}
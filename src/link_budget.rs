@@ -0,0 +1,172 @@
+use eframe::egui;
+use crate::{format_value, SerialApp};
+
+pub struct LinkBudgetState {
+    pub open: bool,
+    tx_antenna_gain_dbi: f64,
+    tx_cable_loss_db: f64,
+    rx_antenna_gain_dbi: f64,
+    rx_cable_loss_db: f64,
+    distance_km: f64,
+    // margin kept for fading, pointing errors and the like when estimating the range
+    required_margin_db: f64,
+}
+
+impl LinkBudgetState {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            tx_antenna_gain_dbi: 0.0,
+            tx_cable_loss_db: 0.0,
+            rx_antenna_gain_dbi: 0.0,
+            rx_cable_loss_db: 0.0,
+            distance_km: 1.0,
+            required_margin_db: 10.0,
+        }
+    }
+}
+
+// FSPL = 20 log10(d / km) + 20 log10(f / MHz) + 32.44 dB
+pub fn free_space_path_loss_db(distance_km: f64, frequency_mhz: f64) -> f64 {
+    20.0 * distance_km.log10() + 20.0 * frequency_mhz.log10() + 32.44
+}
+
+// Distance at which the free space path loss reaches `path_loss_db`
+pub fn free_space_range_km(path_loss_db: f64, frequency_mhz: f64) -> f64 {
+    10_f64.powf((path_loss_db - 32.44 - 20.0 * frequency_mhz.log10()) / 20.0)
+}
+
+// kTB noise in the channel bandwidth, -174 dBm/Hz at room temperature
+pub fn thermal_noise_dbm(bandwidth_khz: f64) -> f64 {
+    -174.0 + 10.0 * (bandwidth_khz * 1000.0).log10()
+}
+
+impl SerialApp {
+    pub(crate) fn show_link_budget_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.link_budget.open;
+        egui::Window::new("Link Budget")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let frequency_mhz = self.channel_frequency();
+                let data_rate = self.dr();
+                let tx_setting = self.tx_power_setting();
+                let sensitivity = self.chip.sensitivity_for_data_rate(data_rate);
+
+                let state = &mut self.link_budget;
+                egui::Grid::new("link_budget_inputs")
+                    .show(ui, |ui| {
+                        ui.label("TX antenna gain");
+                        ui.add(egui::DragValue::new(&mut state.tx_antenna_gain_dbi).speed(0.1).suffix(" dBi"));
+                        ui.end_row();
+
+                        ui.label("TX cable loss");
+                        ui.add(egui::DragValue::new(&mut state.tx_cable_loss_db).speed(0.1).range(0.0..=100.0).suffix(" dB"));
+                        ui.end_row();
+
+                        ui.label("RX antenna gain");
+                        ui.add(egui::DragValue::new(&mut state.rx_antenna_gain_dbi).speed(0.1).suffix(" dBi"));
+                        ui.end_row();
+
+                        ui.label("RX cable loss");
+                        ui.add(egui::DragValue::new(&mut state.rx_cable_loss_db).speed(0.1).range(0.0..=100.0).suffix(" dB"));
+                        ui.end_row();
+
+                        ui.label("Distance");
+                        ui.add(egui::DragValue::new(&mut state.distance_km).speed(0.01).range(0.001..=100_000.0).suffix(" km"));
+                        ui.end_row();
+
+                        ui.label("Required margin");
+                        ui.add(egui::DragValue::new(&mut state.required_margin_db).speed(0.1).range(0.0..=100.0).suffix(" dB"));
+                        ui.end_row();
+                    });
+                ui.separator();
+
+                let Some((sensitivity_rate, sensitivity_dbm)) = sensitivity else {
+                    ui.label(format!("No sensitivity figures for the {}", self.chip.name));
+                    return;
+                };
+                if !tx_setting.characterized {
+                    ui.colored_label(ui.visuals().warn_fg_color, "The PA_TABLE entry in use is not a characterized setting, the TX power is an estimate");
+                }
+                let tx_power = tx_setting.dbm;
+                let state = &self.link_budget;
+                let eirp = tx_power + state.tx_antenna_gain_dbi - state.tx_cable_loss_db;
+                let path_loss = free_space_path_loss_db(state.distance_km, frequency_mhz);
+                let received = eirp - path_loss + state.rx_antenna_gain_dbi - state.rx_cable_loss_db;
+                let margin = received - sensitivity_dbm;
+                // path loss the link can take while keeping the required margin
+                let allowed_path_loss = eirp + state.rx_antenna_gain_dbi - state.rx_cable_loss_db - sensitivity_dbm - state.required_margin_db;
+
+                egui::Grid::new("link_budget_results")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("TX power");
+                        ui.label(if tx_setting.characterized { format!("{} dBm", tx_power) } else { format!("≈ {:.1} dBm (estimate)", tx_power) });
+                        ui.end_row();
+
+                        ui.label("EIRP");
+                        ui.label(format!("{} dBm", format_value(eirp)));
+                        ui.end_row();
+
+                        ui.label("Free space path loss");
+                        ui.label(format!("{} dB at {} MHz", format_value(path_loss), format_value(frequency_mhz)));
+                        ui.end_row();
+
+                        ui.label("Received power");
+                        ui.label(format!("{} dBm", format_value(received)));
+                        ui.end_row();
+
+                        ui.label("Sensitivity");
+                        ui.label(format!("{} dBm (datasheet, {} kBaud)", sensitivity_dbm, sensitivity_rate));
+                        ui.end_row();
+
+                        ui.label("Thermal noise");
+                        ui.label(format!("{} dBm in {} kHz", format_value(thermal_noise_dbm(self.channel_bandwidth())), format_value(self.channel_bandwidth())));
+                        ui.end_row();
+
+                        ui.label("Link margin");
+                        if margin < state.required_margin_db {
+                            ui.colored_label(ui.visuals().warn_fg_color, format!("{} dB", format_value(margin)));
+                        } else {
+                            ui.label(format!("{} dB", format_value(margin)));
+                        }
+                        ui.end_row();
+
+                        ui.label("Maximum range");
+                        ui.label(format!("{} km with {} dB margin", format_value(free_space_range_km(allowed_path_loss, frequency_mhz)), format_value(state.required_margin_db)));
+                        ui.end_row();
+                    });
+            });
+        self.link_budget.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    #[test]
+    fn free_space_path_loss_known_answers() {
+        assert_close(free_space_path_loss_db(1.0, 2400.0), 100.04, 0.01);
+        assert_close(free_space_path_loss_db(10.0, 433.92), 105.19, 0.01);
+        // every doubling of the distance costs 6 dB
+        assert_close(free_space_path_loss_db(2.0, 2400.0) - free_space_path_loss_db(1.0, 2400.0), 6.02, 0.01);
+    }
+
+    #[test]
+    fn range_is_the_inverse_of_the_path_loss() {
+        assert_close(free_space_range_km(100.04, 2400.0), 1.0, 0.001);
+        for distance_km in [0.01, 0.5, 3.0, 250.0] {
+            let path_loss = free_space_path_loss_db(distance_km, 868.3);
+            assert_close(free_space_range_km(path_loss, 868.3), distance_km, distance_km * 1e-9);
+        }
+    }
+
+    #[test]
+    fn thermal_noise_known_answers() {
+        assert_close(thermal_noise_dbm(1000.0), -114.0, 0.001);
+        assert_close(thermal_noise_dbm(203.125), -120.92, 0.01);
+    }
+}
@@ -7,17 +7,28 @@ use std::sync::mpsc;
 use std::thread;
 use std::io::Read;
 use std::time::Duration;
+use std::collections::VecDeque;
 mod structs;
-
-const FREQUENCY_FACTOR: f64 = (2_u32.pow(16)as f64) / 26.0;
-const BASE_FREQUENCY_MIN: f64 = 2400.0;
-const BASE_FREQUENCY_MAX: f64 = 2483.5;
-const DEVIATION_MIN: f64 = 1.6;
-const DEVATION_MAX: f64 = 381.0;
-const DEVIATION_FACTOR: f64 = 26000.0 / 2u32.pow(17)as f64;
-const DATA_RATE_MIN: f64 = 0.025;
-const DATA_RATE_MAX: f64 = 1622.0;
-const DATA_RATE_FACTOR: f64 = 26000.0 / 2u32.pow(28)as f64;
+mod history;
+mod chips;
+mod conversions;
+mod config_file;
+mod diff_view;
+mod status_monitor;
+mod channel_solver;
+mod field_options;
+mod state_machine_view;
+mod agc_view;
+mod foc_view;
+mod gpio_view;
+mod pa_table_view;
+mod validation;
+mod link_budget;
+mod channel_table;
+mod formula_view;
+mod tabs;
+#[cfg(test)]
+mod test_util;
 
 // Commands
 #[repr(u8)]
@@ -37,6 +48,12 @@ struct WriteRegisterFrame  {
     value: u8
 }
 
+#[repr(C)]
+#[derive(Serialize, Deserialize, Debug)]
+struct ReadRegisterFrame  {
+    address: u16
+}
+
 // Base Structure
 #[repr(C)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -45,29 +62,102 @@ struct Packet {
     payload: Vec<u8>,
 }
 
+// What an incoming byte answers, reads are replied to in the order they were sent
+enum PendingRead {
+    PartNumber(u16),
+    DiffRegister { side: usize, generation: u32, name: &'static str },
+    Status(&'static str),
+    Register(&'static str),
+}
+
+// A value typed by the user and what it was encoded to. The conversion error is measured against
+// the typed value, not the text box (which holds the rounded decoded value after every decode)
+#[derive(Clone, Copy)]
+struct RequestedValue {
+    target: f64,
+    achieved: f64,
+}
+
 // this struct is the application struct, declares variables that the application itself can see
 struct SerialApp {
+    // kept so the runtime outlives the serial port it opened
+    #[allow(dead_code)]
     runtime: Runtime,
     port: Option<Box<dyn SerialPort>>,
     rx: mpsc::Receiver<u8>,
     received_data: Vec<u8>,
+    pending_reads: VecDeque<PendingRead>,
+    chip: &'static chips::ChipProfile,
+    chip_detect_message: String,
+    // (address, value) of the PARTNUM probes answered so far
+    partnum_replies: Vec<(u16, u8)>,
+    crystal_hz: u32,
+    config_file_path: String,
+    config_file_message: String,
+    diff: diff_view::DiffState,
+    channel_solver: channel_solver::ChannelSolverState,
+    state_machine_view: state_machine_view::StateMachineView,
+    agc_view: agc_view::AgcView,
+    foc_view: foc_view::FocView,
+    gpio_view: gpio_view::GpioView,
+    pa_table_view: pa_table_view::PaTableView,
+    link_budget: link_budget::LinkBudgetState,
+    channel_table: channel_table::ChannelTableState,
+    formula_view: formula_view::FormulaView,
     register: u8,
     value: u8,
     register_value: structs::RegisterValue,
-    register_address:structs::RegisterAddress,
+    status_value: structs::StatusRegisterValue,
+    status_monitor: status_monitor::StatusMonitor,
+    history: history::History,
+    tabs: tabs::TabsState,
+    // registers before a full read from the radio started, so the whole read becomes one history entry
+    radio_read_before: Option<structs::RegisterValue>,
     user_input_frequency: String,
     user_input_channel_number: u8,
     user_input_mod_scheme: String,
     is_whitened: bool,
     manchester_enabled: bool,
-    user_input_tx_power: i8,
+    // None when the PA_TABLE byte in use isn't one of the chip's characterized settings
+    user_input_tx_power: Option<i8>,
     user_input_phase_transition_time: u8,
     user_input_deviation: String,
     user_input_dr: String,
+    // CHANBW_E and CHANBW_M together, the high nibble of MDMCFG4
+    user_input_chanbw: u8,
+    user_input_channel_spacing: String,
+    user_input_sync_word: String,
+    user_input_sync_mode: u8,
+    user_input_num_preamble: u8,
+    user_input_length_config: u8,
+    crc_enabled: bool,
+    append_status_enabled: bool,
+    user_input_address_check: u8,
+    user_input_pqt: u8,
+    user_input_device_address: u8,
+    user_input_packet_length: u8,
+    user_input_if_frequency: String,
+    user_input_frequency_offset: String,
+    requested_frequency: Option<RequestedValue>,
+    requested_deviation: Option<RequestedValue>,
+    requested_dr: Option<RequestedValue>,
+    // keep h (and optionally channel bandwidth / signal bandwidth) when the data rate changes
+    lock_modulation_index: bool,
+    lock_bandwidth_ratio: bool,
+    // why the last data rate change couldn't keep the locked ratios
+    locked_ratio_message: String,
     invalid_frequency_popup: bool,
     invalid_deviation_popup: bool,
     invalid_dr_popup: bool,
+    invalid_channel_spacing_popup: bool,
+    invalid_channel_frequency_popup: bool,
+    invalid_sync_word_popup: bool,
+    invalid_if_frequency_popup: bool,
+    invalid_frequency_offset_popup: bool,
     is_hex: bool,
+    // fields of the problem selected in the warnings panel, their rows are highlighted
+    highlighted_fields: &'static [&'static str],
+    scroll_to_highlighted_field: bool,
 }
 
 // Implementation of the SerialApp struct further up, declares startup things, such as port selection & initial variable values
@@ -110,109 +200,137 @@ impl SerialApp {
             });
         }
 
-        Self {
+        let register_value = structs::RegisterValue{
+            iocfg2: 0x00,
+            iocfg1: 0x00,
+            iocfg0: 0x00,
+            sync1: 0xD3,
+            sync0: 0x91,
+            pktlen: 0xFF,
+            pktctrl1: 0x04,
+            pktctrl0: 0x45,
+            addr: 0x00,
+            channr: 0x00,
+            fsctrl1: 0x0F,
+            fsctrl0: 0x00,
+            freq2: 0x5E,
+            freq1: 0xC4,
+            freq0: 0xEC,
+            mdmcfg4: 0x8C,
+            mdmcfg3: 0x22,
+            mdmcfg2: 0x02,
+            mdmcfg1: 0x22,
+            mdmcfg0: 0xF8,
+            deviatn: 0x47,
+            mcsm2: 0x07,
+            mcsm1: 0x30,
+            mcsm0: 0x04,
+            foccfg: 0x76,
+            bscfg: 0x6C,
+            agcctrl2: 0x03,
+            agcctrl1: 0x40,
+            agcctrl0: 0x91,
+            frend1: 0x56,
+            frend0: 0x10,
+            fscal3: 0xA9,
+            fscal2: 0x0A,
+            fscal1: 0x20,
+            fscal0: 0x0D,
+            test2: 0x88,
+            test1: 0x11,
+            test0: 0x0B,
+            pa_table0: 0x00,
+            pa_table1: 0x00,
+            pa_table2: 0x00,
+            pa_table3: 0x00,
+            pa_table4: 0x00,
+            pa_table5: 0x00,
+            pa_table6: 0x00,
+            pa_table7: 0x00,
+        };
+
+        let initial = history::Snapshot {
+            registers: register_value,
+            chip: &chips::CC2510,
+            crystal_hz: chips::CC2510.default_crystal_hz,
+        };
+
+        let mut app = Self {
             runtime,
             port,
             rx,
             received_data: Vec::new(),
+            pending_reads: VecDeque::new(),
+            chip: &chips::CC2510,
+            chip_detect_message: String::new(),
+            partnum_replies: Vec::new(),
+            crystal_hz: chips::CC2510.default_crystal_hz,
+            config_file_path: "eos_config.txt".to_string(),
+            config_file_message: String::new(),
+            diff: diff_view::DiffState::new(),
+            channel_solver: channel_solver::ChannelSolverState::new(),
+            state_machine_view: state_machine_view::StateMachineView::new(),
+            agc_view: agc_view::AgcView::new(),
+            foc_view: foc_view::FocView::new(),
+            gpio_view: gpio_view::GpioView::new(),
+            pa_table_view: pa_table_view::PaTableView::new(),
+            link_budget: link_budget::LinkBudgetState::new(),
+            channel_table: channel_table::ChannelTableState::new(),
+            formula_view: formula_view::FormulaView::new(),
             value: 0,
             register: 0,
-            register_value: structs::RegisterValue{
-                iocfg2: 0x00,
-                iocfg1: 0x00,
-                iocfg0: 0x00,
-                sync1: 0xD3,
-                sync0: 0x91,
-                pktlen: 0xFF,
-                pktctrl1: 0x04,
-                pktctrl0: 0x45,
-                addr: 0x00,
-                channr: 0x00,
-                fsctrl1: 0x0F,
-                fsctrl0: 0x00,
-                freq2: 0x5E,
-                freq1: 0xC4,
-                freq0: 0xEC,
-                mdmcfg4: 0x8C,
-                mdmcfg3: 0x22,
-                mdmcfg2: 0x02,
-                mdmcfg1: 0x22,
-                mdmcfg0: 0xF8,
-                deviatn: 0x47,
-                mcsm2: 0x07,
-                mcsm1: 0x30,
-                mcsm0: 0x04,
-                foccfg: 0x76,
-                bscfg: 0x6C,
-                agcctrl2: 0x03,
-                agcctrl1: 0x40,
-                agcctrl0: 0x91,
-                frend1: 0x56,
-                frend0: 0x10,
-                fscal3: 0xA9,
-                fscal2: 0x0A,
-                fscal1: 0x20,
-                fscal0: 0x0D,
-                test2: 0x88,
-                test1: 0x11,
-                test0: 0x0B,
-                pa_table0: 0x00
-            },
-            register_address: structs::RegisterAddress{
-                iocfg2: 0xDF2F,
-                iocfg1: 0xDF30,
-                iocfg0: 0xDF31,
-                sync1: 0xDF00,
-                sync0: 0xDF01,
-                pktlen: 0xDF02,
-                pktctrl1: 0xDF03,
-                pktctrl0: 0xDF04,
-                addr: 0xDF05,
-                channr: 0xDF06,
-                fsctrl1: 0xDF07,
-                fsctrl0: 0xDF08,
-                freq2: 0xDF09,
-                freq1: 0xDF0A,
-                freq0: 0xDF0B,
-                mdmcfg4: 0xDF0C,
-                mdmcfg3: 0xDF0D,
-                mdmcfg2: 0xDF0E,
-                mdmcfg1: 0xDF0F,
-                mdmcfg0: 0xDF10,
-                deviatn: 0xDF11,
-                mcsm2: 0xDF12,
-                mcsm1: 0xDF13,
-                mcsm0: 0xDF14,
-                foccfg: 0xDF15,
-                bscfg: 0xDF16,
-                agcctrl2: 0xDF17,
-                agcctrl1: 0xDF18,
-                agcctrl0: 0xDF19,
-                frend1: 0xDF1A,
-                frend0: 0xDF1B,
-                fscal3: 0xDF1C,
-                fscal2: 0xDF1D,
-                fscal1: 0xDF1E,
-                fscal0: 0xDF1F,
-                test2: 0xDF23,
-                test1: 0xDF24,
-                test0: 0xDF25,
-                pa_table0: 0xDF2E 
-            },
+            register_value,
+            status_value: structs::StatusRegisterValue::default(),
+            status_monitor: status_monitor::StatusMonitor::new(),
+            history: history::History::new(initial),
+            tabs: tabs::TabsState::new(initial),
+            radio_read_before: None,
             user_input_frequency: "2464.0".to_string(),
             user_input_channel_number: 0,
             user_input_mod_scheme: "2-FSK".to_string(),
             is_whitened: true,
             manchester_enabled: false,
-            user_input_tx_power: -55,
+            user_input_tx_power: Some(-55),
             user_input_phase_transition_time: 0,
             user_input_deviation: "47.7".to_string(),
             user_input_dr: "115.051".to_string(),
+            user_input_chanbw: register_value.mdmcfg4 >> 4,
+            user_input_channel_spacing: "199.951".to_string(),
+            user_input_sync_word: format!("{:02X}{:02X}", register_value.sync1, register_value.sync0),
+            user_input_sync_mode: register_value.mdmcfg2 & 0x07,
+            user_input_num_preamble: (register_value.mdmcfg1 & 0x70) >> 4,
+            user_input_length_config: register_value.pktctrl0 & 0x03,
+            crc_enabled: register_value.pktctrl0 & 0x04 != 0,
+            append_status_enabled: register_value.pktctrl1 & 0x04 != 0,
+            user_input_address_check: register_value.pktctrl1 & 0x03,
+            user_input_pqt: register_value.pktctrl1 >> 5,
+            user_input_device_address: register_value.addr,
+            user_input_packet_length: register_value.pktlen,
+            user_input_if_frequency: "380.859".to_string(),
+            user_input_frequency_offset: "0".to_string(),
+            requested_frequency: None,
+            requested_deviation: None,
+            requested_dr: None,
+            lock_modulation_index: false,
+            lock_bandwidth_ratio: false,
+            locked_ratio_message: String::new(),
             invalid_frequency_popup: false,
             invalid_deviation_popup: false,
             invalid_dr_popup: false,
+            invalid_channel_spacing_popup: false,
+            invalid_channel_frequency_popup: false,
+            invalid_sync_word_popup: false,
+            invalid_if_frequency_popup: false,
+            invalid_frequency_offset_popup: false,
             is_hex: true,
-        }
+            highlighted_fields: &[],
+            scroll_to_highlighted_field: false,
+        };
+
+        app.update_parameters_from_registers();
+        // Ask the radio which chip it is as soon as we are connected
+        app.detect_chip();
+        app
     }
 
     fn send_message(&mut self, message: &Packet) -> Result<(), Box<dyn Error>> {
@@ -231,45 +349,160 @@ impl SerialApp {
         Ok(())
     }
 
+    // Switching chips also switches to that chip's usual crystal
+    fn select_chip(&mut self, chip: &'static chips::ChipProfile) {
+        let before = self.snapshot();
+        self.chip = chip;
+        self.crystal_hz = chip.default_crystal_hz;
+        self.record_snapshot_change(before, format!("Chip {} → {}", before.chip.name, chip.name));
+        self.update_parameters_from_registers();
+    }
+
+    fn set_crystal(&mut self, crystal_hz: u32) {
+        let before = self.snapshot();
+        self.crystal_hz = crystal_hz;
+        self.record_snapshot_change(before, format!("Crystal {} → {} MHz", format_value(before.crystal_hz as f64 / 1e6), format_value(crystal_hz as f64 / 1e6)));
+        self.update_parameters_from_registers();
+    }
+
+    // Reads every configuration register of the radio into the editor
+    fn read_radio_registers(&mut self) {
+        if self.port.is_none() {
+            self.config_file_message = "No radio connected".to_string();
+            return;
+        }
+        self.radio_read_before = Some(self.register_value);
+        self.config_file_message = "Reading radio...".to_string();
+        for (name, address) in self.chip.readable_registers() {
+            self.read_register(address, PendingRead::Register(name));
+        }
+    }
+
+    fn handle_radio_register_read(&mut self, name: &'static str, value: u8) {
+        if let Some(register) = self.register_value.field_mut(name) {
+            *register = value;
+        }
+        let still_reading = self.pending_reads.iter().any(|pending| matches!(pending, PendingRead::Register(_)));
+        if !still_reading {
+            if let Some(before) = self.radio_read_before.take() {
+                self.record_change(before, "Read from radio".to_string());
+            }
+            self.update_parameters_from_registers();
+            self.config_file_message = if self.chip.readable_registers().len() < self.chip.register_address.fields().len() {
+                "Read from radio (only PA_TABLE0 can be read back, the other entries are unchanged)".to_string()
+            } else {
+                "Read from radio".to_string()
+            };
+        }
+    }
+
+    // Sets every input widget from the registers, the reverse of the update_*_from_parameter functions.
+    // Called whenever the registers change without going through a widget (undo, loading, reading the radio)
+    fn update_parameters_from_registers(&mut self) {
+        let registers = self.register_value;
+        self.user_input_frequency = format_frequency(self.base_frequency());
+        self.user_input_channel_number = registers.channr;
+        self.user_input_mod_scheme = registers.modulation_name().to_string();
+        self.is_whitened = registers.pktctrl0 & 0x40 != 0;
+        self.manchester_enabled = registers.mdmcfg2 & 0x08 != 0;
+        self.user_input_tx_power = self.chip.pa_setting_for_value(registers.pa_table()[registers.pa_power() as usize], self.channel_frequency()).map(|setting| setting.dbm);
+        self.user_input_phase_transition_time = registers.deviatn & 0x07;
+        self.user_input_deviation = format_value(self.deviation());
+        self.user_input_dr = format_value(self.dr());
+        self.user_input_chanbw = registers.mdmcfg4 >> 4;
+        self.user_input_channel_spacing = format_value(self.channel_spacing());
+        self.user_input_sync_word = format!("{:02X}{:02X}", registers.sync1, registers.sync0);
+        self.user_input_sync_mode = registers.mdmcfg2 & 0x07;
+        self.user_input_num_preamble = (registers.mdmcfg1 & 0x70) >> 4;
+        self.user_input_length_config = registers.pktctrl0 & 0x03;
+        self.crc_enabled = registers.pktctrl0 & 0x04 != 0;
+        self.append_status_enabled = registers.pktctrl1 & 0x04 != 0;
+        self.user_input_address_check = registers.pktctrl1 & 0x03;
+        self.user_input_pqt = registers.pktctrl1 >> 5;
+        self.user_input_device_address = registers.addr;
+        self.user_input_packet_length = registers.pktlen;
+        self.user_input_if_frequency = format_value(self.if_frequency());
+        self.user_input_frequency_offset = format_value(self.frequency_offset());
+    }
+
+    fn read_register(&mut self, address: u16, pending: PendingRead) {
+        if self.port.is_none() {
+            return;
+        }
+        let packet = Packet {
+            command_id: CommandID::ReadRegister,
+            payload: postcard::to_allocvec(&ReadRegisterFrame { address }).expect("Failed to serialize packet"),
+        };
+        if self.send_message(&packet).is_ok() {
+            self.pending_reads.push_back(pending);
+        }
+    }
+
+    // PARTNUM is at a different address on the SoC and the SPI parts, so every address map is probed
+    fn detect_chip(&mut self) {
+        self.chip_detect_message = "Detecting...".to_string();
+        if self.port.is_none() {
+            self.chip_detect_message = "No radio connected".to_string();
+        }
+        self.partnum_replies.clear();
+        for address in chips::partnum_addresses() {
+            self.read_register(address, PendingRead::PartNumber(address));
+        }
+    }
+
+    fn handle_partnum_read(&mut self, address: u16, value: u8) {
+        self.partnum_replies.push((address, value));
+        let still_probing = self.pending_reads.iter().any(|pending| matches!(pending, PendingRead::PartNumber(_)));
+        if still_probing {
+            return;
+        }
+        let replies = std::mem::take(&mut self.partnum_replies);
+        match replies.iter().find_map(|(address, value)| chips::from_partnum_at(*address, *value).map(|chip| (chip, *value))) {
+            Some((chip, partnum)) => {
+                self.status_value.partnum = partnum;
+                self.select_chip(chip);
+                self.chip_detect_message = format!("Detected {}", chip.name);
+            }
+            None => {
+                let values: Vec<String> = replies.iter().map(|(address, value)| format!("0x{:02X} at 0x{:X}", value, address)).collect();
+                self.chip_detect_message = format!("Unknown PARTNUM {}", values.join(", "));
+            }
+        }
+    }
+
+    fn handle_register_read(&mut self, pending: PendingRead, value: u8) {
+        match pending {
+            PendingRead::PartNumber(address) => self.handle_partnum_read(address, value),
+            PendingRead::DiffRegister { side, generation, name } => self.handle_diff_register_read(side, generation, name, value),
+            PendingRead::Status(name) => self.handle_status_register_read(name, value),
+            PendingRead::Register(name) => self.handle_radio_register_read(name, value),
+        }
+    }
+
     fn update_base_frequency_from_parameter(&mut self) {
-        let intermediate_input_frequency_u64 = f64::floor(self.user_input_frequency.parse::<f64>().unwrap() * FREQUENCY_FACTOR) as u64; 
-        self.register_value.freq0 = (intermediate_input_frequency_u64 & 0xFF) as u8;
-        self.register_value.freq1 = ((intermediate_input_frequency_u64 >> 8) & 0xFF) as u8;
-        self.register_value.freq2 = ((intermediate_input_frequency_u64 >> 16) & 0xFF) as u8;
-        u64::from_str_radix(format!("{}{}{}", 
-            format!("{:08b}", self.register_value.freq2).to_string(), 
-            format!("{:08b}", self.register_value.freq1).to_string(), 
-            format!("{:08b}", self.register_value.freq0))
-            .as_str(), 2).expect("Invalid binary string")
-            .to_string();
+        let Ok(frequency) = self.user_input_frequency.trim().parse::<f64>() else {
+            return;
+        };
+        let freq = conversions::frequency_to_register(frequency, self.crystal_hz);
+        self.register_value.freq0 = freq as u8;
+        self.register_value.freq1 = (freq >> 8) as u8;
+        self.register_value.freq2 = (freq >> 16) as u8;
+        self.requested_frequency = Some(RequestedValue { target: frequency, achieved: self.base_frequency() });
     }
     
+    // Output power of the PA_TABLE entry in use, estimated from the datasheet table when the byte isn't listed
+    fn tx_power_setting(&self) -> chips::PaEstimate {
+        let value = self.register_value.pa_table()[self.register_value.pa_power() as usize];
+        self.chip.estimate_pa_setting(value, self.channel_frequency())
+    }
+
     fn update_channel_number_from_parameter(&mut self) {
         self.register_value.channr = self.user_input_channel_number
     }
     
     fn update_tx_power_from_parameter(&mut self) {
-        self.register_value.pa_table0 = 0x00;
-        match self.user_input_tx_power {
-            1 => self.register_value.pa_table0 = 0xFF,
-            0 => self.register_value.pa_table0 = 0xFE,
-            -2 => self.register_value.pa_table0 = 0xBF,
-            -4 => self.register_value.pa_table0 = 0xAA,
-            -6 => self.register_value.pa_table0 = 0x7F,
-            -8 => self.register_value.pa_table0 = 0x99,
-            -10 => self.register_value.pa_table0 = 0xCB,
-            -12 => self.register_value.pa_table0 = 0x95,
-            -14 => self.register_value.pa_table0 = 0x59,
-            -16 => self.register_value.pa_table0 = 0x87,
-            -18 => self.register_value.pa_table0 = 0xC8,
-            -20 => self.register_value.pa_table0 = 0xC1,
-            -22 => self.register_value.pa_table0 = 0x83,
-            -24 => self.register_value.pa_table0 = 0x53,
-            -26 => self.register_value.pa_table0 = 0x54,
-            -28 => self.register_value.pa_table0 = 0x41,
-            -30 => self.register_value.pa_table0 = 0x44,
-            -55 => self.register_value.pa_table0 = 0x00,
-            _ => self.register_value.pa_table0 = self.register_value.pa_table0,
+        if let Some(value) = self.user_input_tx_power.and_then(|dbm| self.chip.pa_value_for_dbm(dbm, self.channel_frequency())) {
+            *self.register_value.pa_table_entry_mut(self.register_value.pa_power()) = value;
         }
     }
     
@@ -280,7 +513,16 @@ impl SerialApp {
             "2-FSK" => self.register_value.mdmcfg2 |= 0x00,
             "GFSK" => self.register_value.mdmcfg2 |= 0x10,
             "MSK" => self.register_value.mdmcfg2 |= 0x70,
-            _ => self.register_value.mdmcfg2 = self.register_value.mdmcfg2,
+            "ASK/OOK" => {
+                self.register_value.mdmcfg2 |= 0x30;
+                // a 0 is sent with PA_TABLE0 and a 1 with the PA_POWER entry, so the output power moves to PA_TABLE1 and a 0 turns the PA off
+                if self.register_value.pa_power() == 0 {
+                    self.register_value.pa_table1 = self.register_value.pa_table0;
+                    self.register_value.pa_table0 = 0x00;
+                    self.register_value.frend0 = (self.register_value.frend0 & 0xF8) | 0x01;
+                }
+            }
+            _ => {}
         }
     }
     
@@ -293,6 +535,8 @@ impl SerialApp {
     }
     
     fn update_phase_transition_time_from_parameter(&mut self) {
+        // the old value has to be cleared first, or switching from 5 to 2 would leave 7
+        self.register_value.deviatn &= 0xF8;
         match self.user_input_phase_transition_time {
             0 => self.register_value.deviatn |= 0x00,
             1 => self.register_value.deviatn |= 0x01,
@@ -302,243 +546,430 @@ impl SerialApp {
             5 => self.register_value.deviatn |= 0x05,
             6 => self.register_value.deviatn |= 0x06,
             7 => self.register_value.deviatn |= 0x07,
-            _ => self.register_value.deviatn = self.register_value.deviatn,
+            _ => {}
         }
     }
     
     fn update_deviation_from_parameter(&mut self) {
-        let intermediate_deviation_u64 = f64::floor(self.user_input_deviation.parse::<f64>().unwrap() / DEVIATION_FACTOR) as u64;
-        let deviation_e = (intermediate_deviation_u64 / 8).checked_ilog2().unwrap() as u8;
-        let deviation_m = ((intermediate_deviation_u64 / 2u64.pow(deviation_e as u32)) % 8) as u8;
+        let Ok(deviation) = self.user_input_deviation.trim().parse::<f64>() else {
+            return;
+        };
+        self.set_deviation(deviation);
+    }
+
+    fn set_deviation(&mut self, deviation: f64) {
+        let (deviation_e, deviation_m) = conversions::deviation_to_register(deviation, self.crystal_hz);
         self.register_value.deviatn = deviation_e << 4 | deviation_m;
+        self.requested_deviation = Some(RequestedValue { target: deviation, achieved: self.deviation() });
     }
     
     fn update_dr_from_parameter(&mut self) {
+        let Ok(dr) = self.user_input_dr.trim().parse::<f64>() else {
+            return;
+        };
         self.register_value.mdmcfg4 &= 0xF0;
-        let intermediate_dr_u64 = f64::floor(self.user_input_dr.parse::<f64>().unwrap() / DATA_RATE_FACTOR) as u64;
-        let dr_e = (intermediate_dr_u64 / 256).checked_ilog2().unwrap() as u8;
-        let dr_m = ((intermediate_dr_u64 / 2u64.pow(dr_e as u32)) % 256) as u8;
+        let (dr_e, dr_m) = conversions::data_rate_to_register(dr, self.crystal_hz);
         self.register_value.mdmcfg4 |= dr_e;
         self.register_value.mdmcfg3 = dr_m;
+        self.requested_dr = Some(RequestedValue { target: dr, achieved: self.dr() });
+    }
+
+    fn update_channel_bandwidth_from_parameter(&mut self) {
+        self.register_value.mdmcfg4 &= 0x0F;
+        self.register_value.mdmcfg4 |= (self.user_input_chanbw & 0x0F) << 4;
+    }
+
+    fn update_channel_spacing_from_parameter(&mut self) {
+        let Ok(channel_spacing) = self.user_input_channel_spacing.trim().parse::<f64>() else {
+            return;
+        };
+        let (chanspc_e, chanspc_m) = conversions::channel_spacing_to_register(channel_spacing, self.crystal_hz);
+        self.register_value.mdmcfg1 &= 0xFC;
+        self.register_value.mdmcfg1 |= chanspc_e;
+        self.register_value.mdmcfg0 = chanspc_m;
+    }
+
+    fn update_sync_word_from_parameter(&mut self) {
+        let Some(sync_word) = parse_sync_word(&self.user_input_sync_word) else {
+            return;
+        };
+        self.register_value.sync1 = (sync_word >> 8) as u8;
+        self.register_value.sync0 = (sync_word & 0xFF) as u8;
+    }
+
+    fn update_sync_mode_from_parameter(&mut self) {
+        self.register_value.mdmcfg2 &= 0xF8;
+        self.register_value.mdmcfg2 |= self.user_input_sync_mode & 0x07;
+    }
+
+    fn update_num_preamble_from_parameter(&mut self) {
+        self.register_value.mdmcfg1 &= 0x8F;
+        self.register_value.mdmcfg1 |= (self.user_input_num_preamble & 0x07) << 4;
+    }
+
+    fn update_if_frequency_from_parameter(&mut self) {
+        let Ok(if_frequency) = self.user_input_if_frequency.trim().parse::<f64>() else {
+            return;
+        };
+        self.register_value.fsctrl1 &= 0xE0;
+        self.register_value.fsctrl1 |= conversions::if_frequency_to_register(if_frequency, self.crystal_hz);
     }
 
-    // fn update_base_frequency_from_parameter(&mut self) {
-    //     let intermediate_input_frequency_u64 = f64::floor(self.user_input_frequency.parse::<f64>().unwrap() * FREQUENCY_FACTOR) as u64; 
-    //     self.register_value.freq0 = (intermediate_input_frequency_u64 & 0xFF) as u8;
-    //     self.register_value.freq1 = ((intermediate_input_frequency_u64 >> 8) & 0xFF) as u8;
-    //     self.register_value.freq2 = ((intermediate_input_frequency_u64 >> 16) & 0xFF) as u8;
-    //     u64::from_str_radix(format!("{}{}{}", format!("{:08b}", self.register_value.freq2).to_string(), format!("{:08b}", self.register_value.freq1).to_string(), format!("{:08b}", self.register_value.freq0)).as_str(), 2).expect("Invalid binary string").to_string();
-    // }
+    fn update_frequency_offset_from_parameter(&mut self) {
+        let Ok(frequency_offset) = self.user_input_frequency_offset.trim().parse::<f64>() else {
+            return;
+        };
+        self.register_value.fsctrl0 = conversions::frequency_offset_to_register(frequency_offset, self.crystal_hz);
+    }
+
+    fn update_length_config_from_parameter(&mut self) {
+        self.register_value.pktctrl0 &= 0xFC;
+        self.register_value.pktctrl0 |= self.user_input_length_config & 0x03;
+    }
+
+    fn update_crc_from_parameter(&mut self) {
+        if self.crc_enabled {self.register_value.pktctrl0 |= 0x04} else {self.register_value.pktctrl0 &= 0xFB};
+    }
+
+    fn update_append_status_from_parameter(&mut self) {
+        if self.append_status_enabled {self.register_value.pktctrl1 |= 0x04} else {self.register_value.pktctrl1 &= 0xFB};
+    }
+
+    fn update_address_check_from_parameter(&mut self) {
+        self.register_value.pktctrl1 &= 0xFC;
+        self.register_value.pktctrl1 |= self.user_input_address_check & 0x03;
+    }
+
+    fn update_pqt_from_parameter(&mut self) {
+        self.register_value.pktctrl1 &= 0x1F;
+        self.register_value.pktctrl1 |= (self.user_input_pqt & 0x07) << 5;
+    }
+
+    fn update_device_address_from_parameter(&mut self) {
+        self.register_value.addr = self.user_input_device_address;
+    }
+
+    fn update_packet_length_from_parameter(&mut self) {
+        self.register_value.pktlen = self.user_input_packet_length;
+    }
+
+    fn base_frequency(&self) -> f64 {
+        self.register_value.base_frequency(self.crystal_hz)
+    }
+
+    fn deviation(&self) -> f64 {
+        self.register_value.deviation(self.crystal_hz)
+    }
+
+    fn dr(&self) -> f64 {
+        self.register_value.data_rate(self.crystal_hz)
+    }
+
+    fn channel_bandwidth(&self) -> f64 {
+        self.register_value.channel_bandwidth(self.crystal_hz)
+    }
+
+    fn channel_spacing(&self) -> f64 {
+        self.register_value.channel_spacing(self.crystal_hz)
+    }
+
+    fn if_frequency(&self) -> f64 {
+        conversions::register_to_if_frequency(self.register_value.fsctrl1, self.crystal_hz)
+    }
+
+    fn frequency_offset(&self) -> f64 {
+        conversions::register_to_frequency_offset(self.register_value.fsctrl0, self.crystal_hz)
+    }
+
+    fn channel_frequency(&self) -> f64 {
+        self.register_value.channel_frequency(self.crystal_hz)
+    }
+
+    fn save_configuration(&mut self) {
+        let config = config_file::Configuration {
+            chip_name: self.chip.name.to_string(),
+            crystal_hz: self.crystal_hz,
+            registers: self.register_value,
+        };
+        self.config_file_message = match config_file::save(&self.config_file_path, &config) {
+            Ok(()) => format!("Saved {}", self.config_file_path),
+            Err(e) => format!("Failed to save: {}", e),
+        };
+    }
+
+    fn load_configuration(&mut self) {
+        match config_file::load(&self.config_file_path, self.register_value) {
+            Ok(config) => {
+                // the registers only mean something for the chip they were written for
+                let Some(chip) = chips::ALL_CHIPS.into_iter().find(|chip| chip.name == config.chip_name) else {
+                    self.config_file_message = if config.chip_name.is_empty() {
+                        "Failed to load: the file has no CHIP line".to_string()
+                    } else {
+                        format!("Failed to load: unknown CHIP {}", config.chip_name)
+                    };
+                    return;
+                };
+                let before = self.snapshot();
+                self.chip = chip;
+                self.crystal_hz = config.crystal_hz;
+                self.register_value = config.registers;
+                self.record_snapshot_change(before, format!("Loaded {}", self.config_file_path));
+                self.update_parameters_from_registers();
+                self.config_file_message = format!("Loaded {}", self.config_file_path);
+            }
+            Err(e) => self.config_file_message = format!("Failed to load: {}", e),
+        }
+    }
 
     fn print_concatenated_freq(&self) -> String {
-        let intermediate_decimal_frequency = ((self.register_value.freq2 as u32) << 16) | ((self.register_value.freq1 as u32) << 8) | self.register_value.freq0 as u32;
-        format!("{}", (intermediate_decimal_frequency as f64 / FREQUENCY_FACTOR))
+        format!("{}", self.base_frequency())
     }
     
     fn print_deviation(&self) -> String {
-        let register_deviatn_m = self.register_value.deviatn & 0x07;
-        let register_deviatn_e = (self.register_value.deviatn & 0x70) >> 4;
-        let intermediate_decimal_deviation = DEVIATION_FACTOR * ((8 + register_deviatn_m) as u64 * 2u64.pow(register_deviatn_e as u32)) as f64;
-        intermediate_decimal_deviation.to_string()
+        self.deviation().to_string()
     }
     
     fn print_dr(&self) -> String {
-        let register_dr_e = self.register_value.mdmcfg4 & 0x0F;
-        let register_dr_m = self.register_value.mdmcfg3;
-        let intermediate_decimal_dr = DATA_RATE_FACTOR * ((256 + register_dr_m as u64) * 2u64.pow(register_dr_e as u32)) as f64;
-        intermediate_decimal_dr.to_string()
+        self.dr().to_string()
     }
 
-    fn frequency_input_is_out_of_bounds(&mut self) {
-        if let Ok(value) = self.user_input_frequency.trim().parse::<f64>() {
-            // Check if the value is out of bounds
-            if value < BASE_FREQUENCY_MIN || value > BASE_FREQUENCY_MAX {
-                self.invalid_frequency_popup = true; // Trigger the popup
-            } else {
-                self.update_base_frequency_from_parameter();
-                self.invalid_frequency_popup = false;
-            }
-        } else {
-            // Show popup for invalid input
-            self.invalid_frequency_popup = true;
-        } 
+    fn snapshot(&self) -> history::Snapshot {
+        history::Snapshot {
+            registers: self.register_value,
+            chip: self.chip,
+            crystal_hz: self.crystal_hz,
+        }
     }
 
-    fn show_invalid_frequency_popup(&mut self, ctx: &egui::Context) {
-        egui::Window::new("Invalid Frequency Input")
-            .collapsible(false)
-            .resizable(false)
-            .show(ctx, |ui| {
-                ui.label(format!("The base frequency must be between {:?} and {:?}!", BASE_FREQUENCY_MIN, BASE_FREQUENCY_MAX));
-                if ui.button("OK").clicked() {
-                    self.invalid_frequency_popup = false; // Close the popup
-                }
-        });
+    // Pushes a history entry if the registers changed since `before` was taken
+    fn record_change(&mut self, before: structs::RegisterValue, description: String) {
+        if self.register_value != before {
+            self.history.push(description, self.snapshot());
+        }
     }
 
-    fn deviation_input_is_out_of_bounds(&mut self) {
-        if let Ok(value) = self.user_input_deviation.trim().parse::<f64>() {
-            // Check if the value is out of bounds
-            if value < DEVIATION_MIN || value > DEVATION_MAX {
-                self.invalid_deviation_popup = true; // Trigger the popup
-            } else {
-                self.update_deviation_from_parameter();
-                self.invalid_deviation_popup = false;
-            }
-        } else {
-            // Show popup for invalid input
-            self.invalid_deviation_popup = true;
+    // Same as record_change, for changes that can also switch the chip or crystal
+    fn record_snapshot_change(&mut self, before: history::Snapshot, description: String) {
+        if self.snapshot() != before {
+            self.history.push(description, self.snapshot());
         }
     }
 
-    fn show_invalid_deviation_popup(&mut self, ctx: &egui::Context) {
-        egui::Window::new("Invalid Deviation Input")
-            .collapsible(false)
-            .resizable(false)
-            .show(ctx, |ui| {
-                ui.label(format!("The deviation must be between {:?} and {:?}!", DEVIATION_MIN, DEVATION_MAX));
-                if ui.button("OK").clicked() {
-                    self.invalid_deviation_popup = false; // Close the popup
+    fn restore_snapshot(&mut self, snapshot: history::Snapshot) {
+        self.register_value = snapshot.registers;
+        self.chip = snapshot.chip;
+        self.crystal_hz = snapshot.crystal_hz;
+        self.update_parameters_from_registers();
+    }
+
+    // One grid row with a combo box for a register bit field, `shift` and `mask` locate the field inside the register
+    fn register_field_combo<S: AsRef<str>>(&mut self, ui: &mut egui::Ui, register: &'static str, label: &str, options: &[S], shift: u8, mask: u8) {
+        let Some(register_value) = self.register_value.field(register) else {
+            return;
+        };
+        let old_value = (register_value >> shift) & mask;
+        let mut value = old_value;
+        ui.label(label);
+        egui::ComboBox::from_id_salt((register, label))
+            .selected_text(options[value as usize].as_ref())
+            .width(260.0)
+            .show_ui(ui, |ui| {
+                for (option, name) in options.iter().enumerate() {
+                    ui.selectable_value(&mut value, option as u8, name.as_ref());
                 }
         });
-    }
+        ui.label(format!("{} {:08b}", register, register_value));
+        ui.end_row();
 
-    fn dr_input_is_out_of_bounds(&mut self) {
-        if let Ok(value) = self.user_input_dr.trim().parse::<f64>() {
-            // Check if the value is out of bounds
-            if value < DATA_RATE_MIN || value > DATA_RATE_MAX {
-                self.invalid_dr_popup = true; // Trigger the popup
-            } else {
-                self.update_dr_from_parameter();
-                self.invalid_dr_popup = false;
+        if value != old_value {
+            let before = self.register_value;
+            if let Some(field) = self.register_value.field_mut(register) {
+                *field = (*field & !(mask << shift)) | (value << shift);
             }
-        } else {
-            // Show popup for invalid input
-            self.invalid_dr_popup = true;
+            self.record_change(before, format!("{} {} → {}", label, options[old_value as usize].as_ref(), options[value as usize].as_ref()));
+            self.update_parameters_from_registers();
         }
     }
 
-    fn show_invalid_dr_popup(&mut self, ctx: &egui::Context) {
-        egui::Window::new("Invalid Data Rate Input")
-            .collapsible(false)
-            .resizable(false)
-            .show(ctx, |ui: &mut egui::Ui| {
-                ui.label(format!("The data rate must be between {:?} and {:?}!", DATA_RATE_MIN, DATA_RATE_MAX));
-                if ui.button("OK").clicked() {
-                    self.invalid_dr_popup = false; // Close the popup
-                }
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo() {
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo() {
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    fn jump_to_history_entry(&mut self, index: usize) {
+        if let Some(snapshot) = self.history.jump_to(index) {
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    fn handle_history_shortcuts(&mut self, ctx: &egui::Context) {
+        // Most specific shortcut first, Ctrl+Z would also match Ctrl+Shift+Z
+        let redo = ctx.input_mut(|i| {
+            i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z)
+                || i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)
         });
+        let undo = ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z));
+        if redo {
+            self.redo();
+        } else if undo {
+            self.undo();
+        }
     }
-}
 
-// implementation of the UI for SerialApp
-impl eframe::App for SerialApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Check for received data
-        while let Ok(byte) = self.rx.try_recv() {
-            self.received_data.push(byte);
+    fn show_history_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading("History");
+        ui.horizontal(|ui| {
+            if ui.add_enabled(self.history.can_undo(), egui::Button::new("Undo")).on_hover_text("Ctrl+Z").clicked() {
+                self.undo();
+            }
+            if ui.add_enabled(self.history.can_redo(), egui::Button::new("Redo")).on_hover_text("Ctrl+Y / Ctrl+Shift+Z").clicked() {
+                self.redo();
+            }
+        });
+        ui.separator();
+
+        let mut clicked_entry = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, entry) in self.history.entries().iter().enumerate() {
+                let is_current = index == self.history.current();
+                let label = if index > self.history.current() {
+                    // entries that would be redone are greyed out
+                    egui::RichText::new(&entry.description).weak()
+                } else {
+                    egui::RichText::new(&entry.description)
+                };
+                if ui.selectable_label(is_current, label).clicked() {
+                    clicked_entry = Some(index);
+                }
+            }
+        });
+        if let Some(index) = clicked_entry {
+            self.jump_to_history_entry(index);
         }
+    }
 
-        egui::SidePanel::left("left_panel").show(ctx, |ui| {
-            egui::Grid::new("left_panels")
-                .min_col_width(150.0)
-                .striped(true)
-                .show(ui, |ui| {
-                    ui.label("Base Frequency");
-                    ui.vertical(|ui| {
-                        let frequency_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_frequency).desired_width(68.0));
-                        if frequency_text_box.lost_focus() {
-                            self.frequency_input_is_out_of_bounds();
-                        }
-                        if self.invalid_frequency_popup {
-                            self.show_invalid_frequency_popup(ctx);
-                        }
-                        ui.add(
-                            egui::TextEdit::singleline(&mut self.print_concatenated_freq()).clip_text(true).desired_width(68.0)
-                        );
-                    });
-                    ui.label("MHz");
+    // The parameter rows of the left panel
+    fn show_parameters_grid(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        egui::Grid::new("left_panels")
+            .min_col_width(150.0)
+            .striped(true)
+            .show(ui, |ui| {
+                self.parameter_label(ui, "Base Frequency");
+                ui.vertical(|ui| {
+                    let frequency_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_frequency).desired_width(68.0));
+                    if frequency_text_box.lost_focus() {
+                        self.frequency_input_is_out_of_bounds();
+                    }
+                    if self.invalid_frequency_popup {
+                        self.show_invalid_frequency_popup(ctx);
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.print_concatenated_freq()).clip_text(true).desired_width(68.0)
+                    );
+                    ui.label(conversion_error_text(self.requested_frequency, self.base_frequency(), 1_000_000.0, "Hz"));
+                });
+                ui.label("MHz");
                 ui.end_row();
 
-                ui.label("Channel Number");
+                self.parameter_label(ui, "Channel Number");
                 ui.horizontal(|ui| {
                     let channel_number_box = ui.add(egui::DragValue::new(&mut self.user_input_channel_number)
                         .speed(1.0)
                         .clamp_existing_to_range(true)
                         .range(0..=255));
                     if channel_number_box.changed() {
-                        self.update_channel_number_from_parameter();
+                        self.channel_number_is_out_of_bounds();
+                    }
+                    if self.invalid_channel_frequency_popup {
+                        self.show_invalid_channel_frequency_popup(ctx);
                     }
                 });
                 ui.label(self.register_value.channr.to_string());
                 ui.end_row();
 
-                ui.label("Modulation Scheme");
+                self.parameter_label(ui, "Modulation Scheme");
                 ui.horizontal(|ui| {
+                    let old_mod_scheme = self.user_input_mod_scheme.clone();
                     egui::ComboBox::from_label("")
                         .selected_text(&self.user_input_mod_scheme)
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut self.user_input_mod_scheme, "2-FSK".to_string(), "2-FSK");
                             ui.selectable_value(&mut self.user_input_mod_scheme, "GFSK".to_string(), "GFSK");
-                            ui.selectable_value(&mut self.user_input_mod_scheme, "MSK".to_string(), "MSK")
+                            ui.selectable_value(&mut self.user_input_mod_scheme, "MSK".to_string(), "MSK");
+                            if self.chip.supports_ask_ook {
+                                ui.selectable_value(&mut self.user_input_mod_scheme, "ASK/OOK".to_string(), "ASK/OOK");
+                            }
                     });
-                    self.update_modulation_scheme_from_parameter();
+                    if self.user_input_mod_scheme != old_mod_scheme {
+                        let before = self.register_value;
+                        self.update_modulation_scheme_from_parameter();
+                        self.record_change(before, format!("Modulation {} → {}", old_mod_scheme, self.user_input_mod_scheme));
+                    }
                 });
                 ui.end_row();
 
-                ui.label("Data Whitening");
+                self.parameter_label(ui, "Data Whitening");
                 ui.horizontal(|ui| {
                     if ui.checkbox(&mut self.is_whitened, "Data Whitening").clicked() {
+                        let before = self.register_value;
                         self.update_data_whitening_from_parameter();
+                        self.record_change(before, format!("Data whitening {}", if self.is_whitened {"off → on"} else {"on → off"}));
                     }
                 });
                 ui.label(self.register_value.pktctrl0.to_string());
                 ui.end_row();
 
-                ui.label("Manchester Enable");
+                self.parameter_label(ui, "Manchester Enable");
                 ui.horizontal(|ui| {
                     if ui.checkbox(&mut self.manchester_enabled, "Manchester Enable").clicked() {
+                        let before = self.register_value;
                         self.update_manchester_from_parameter();
+                        self.record_change(before, format!("Manchester encoding {}", if self.manchester_enabled {"off → on"} else {"on → off"}));
                     }
                 });
                 ui.label(self.register_value.mdmcfg2.to_string());
                 ui.end_row();
-                
-                ui.label("TX Power");
-                ui.horizontal(|ui| {
-                    egui::ComboBox::from_label(" ")
-                        .selected_text(&self.user_input_tx_power.to_string())
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(&mut self.user_input_tx_power, 1, "1");
-                            ui.selectable_value(&mut self.user_input_tx_power, 0, "0");
-                            ui.selectable_value(&mut self.user_input_tx_power, -2, "-2");
-                            ui.selectable_value(&mut self.user_input_tx_power, -4, "-4");
-                            ui.selectable_value(&mut self.user_input_tx_power, -6, "-6");
-                            ui.selectable_value(&mut self.user_input_tx_power, -8, "-8");
-                            ui.selectable_value(&mut self.user_input_tx_power, -10, "-10");
-                            ui.selectable_value(&mut self.user_input_tx_power, -12, "-12");
-                            ui.selectable_value(&mut self.user_input_tx_power, -14, "-14");
-                            ui.selectable_value(&mut self.user_input_tx_power, -16, "-16");
-                            ui.selectable_value(&mut self.user_input_tx_power, -18, "-18");
-                            ui.selectable_value(&mut self.user_input_tx_power, -20, "-20");
-                            ui.selectable_value(&mut self.user_input_tx_power, -22, "-22");
-                            ui.selectable_value(&mut self.user_input_tx_power, -24, "-24");
-                            ui.selectable_value(&mut self.user_input_tx_power, -26, "-26");
-                            ui.selectable_value(&mut self.user_input_tx_power, -28, "-28");
-                            ui.selectable_value(&mut self.user_input_tx_power, -30, "-30");
-                            ui.selectable_value(&mut self.user_input_tx_power, -55, "-55");
-                    });
-                    self.update_tx_power_from_parameter();
-                    
+
+                self.parameter_label(ui, "TX Power");
+                ui.vertical(|ui| {
+                    let frequency = self.channel_frequency();
+                    let (min_dbm, max_dbm) = self.chip.pa_dbm_range(frequency);
+                    let setting = self.tx_power_setting();
+                    let mut dbm = self.user_input_tx_power.unwrap_or(min_dbm) as f64;
+                    // the slider always jumps to the closest characterized setting
+                    if ui.add(egui::Slider::new(&mut dbm, min_dbm as f64..=max_dbm as f64).suffix(" dBm")).changed() {
+                        let old_tx_power = self.user_input_tx_power;
+                        let snapped = self.chip.nearest_pa_setting(dbm, frequency);
+                        if Some(snapped.dbm) != old_tx_power {
+                            let before = self.register_value;
+                            self.user_input_tx_power = Some(snapped.dbm);
+                            self.update_tx_power_from_parameter();
+                            self.record_change(before, format!("TX power {} → {} dBm", tx_power_text(old_tx_power), snapped.dbm));
+                        }
+                    }
+                    if setting.characterized {
+                        ui.label(setting.text());
+                    } else {
+                        ui.colored_label(ui.visuals().warn_fg_color, setting.text())
+                            .on_hover_text("The PA byte isn't in the datasheet table, the output power is interpolated between the listed bytes either side");
+                    }
                 });
-                ui.label(self.register_value.pa_table0.to_string());
+                ui.label(format!("PA_TABLE{} {}", self.register_value.pa_power(), self.register_value.pa_table()[self.register_value.pa_power() as usize]));
                 ui.end_row();
 
                 if self.register_value.mdmcfg2 & 0x70 == 0x70 {
                     ui.label("Phase Transition Time");
                     ui.horizontal(|ui| {
+                        let old_phase_transition_time = self.user_input_phase_transition_time;
                         egui::ComboBox::from_label("Phase Transition Time")
-                            .selected_text(&self.user_input_phase_transition_time.to_string())
+                            .selected_text(self.user_input_phase_transition_time.to_string())
                             .show_ui(ui, |ui| {
                                 ui.selectable_value(&mut self.user_input_phase_transition_time, 0, "0");
                                 ui.selectable_value(&mut self.user_input_phase_transition_time, 1, "1");
@@ -549,10 +980,14 @@ impl eframe::App for SerialApp {
                                 ui.selectable_value(&mut self.user_input_phase_transition_time, 6, "6");
                                 ui.selectable_value(&mut self.user_input_phase_transition_time, 7, "7");
                         });
-                        self.update_phase_transition_time_from_parameter();
+                        if self.user_input_phase_transition_time != old_phase_transition_time {
+                            let before = self.register_value;
+                            self.update_phase_transition_time_from_parameter();
+                            self.record_change(before, format!("Phase transition time {} → {}", old_phase_transition_time, self.user_input_phase_transition_time));
+                        }
                     });
                 } else {
-                    ui.label("Deviation");
+                    self.parameter_label(ui, "Deviation");
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
                             let deviation_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_deviation).desired_width(68.0));
@@ -566,13 +1001,14 @@ impl eframe::App for SerialApp {
                         ui.add(
                             egui::TextEdit::singleline(&mut self.print_deviation()).clip_text(true).desired_width(68.0)
                         ); 
+                        ui.label(conversion_error_text(self.requested_deviation, self.deviation(), 1_000.0, "Hz"));
                     });
                     ui.label(self.register_value.deviatn.to_string());
                     // ui.label(format!("register_deviatn_m = {:08b} ---> :{:?}", self.register_value.deviatn, self.register_value.deviatn));
                 }
                 ui.end_row();
 
-                ui.label("Data Rate");
+                self.parameter_label(ui, "Data Rate");
                 ui.vertical(|ui| {
                     ui.horizontal(|ui| {
                         let dr_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_dr).desired_width(68.0));
@@ -586,6 +1022,7 @@ impl eframe::App for SerialApp {
                     ui.add(
                         egui::TextEdit::singleline(&mut self.print_dr()).clip_text(true).desired_width(68.0)
                     ); 
+                    ui.label(conversion_error_text(self.requested_dr, self.dr(), 1_000.0, "Baud"));
                 });
                 ui.label((self.register_value.mdmcfg4 & 0x0F).to_string());
                 ui.label(self.register_value.mdmcfg3.to_string());
@@ -593,45 +1030,744 @@ impl eframe::App for SerialApp {
                 // ui.label(format!("register_mdmcfg4 = {:08b}", self.register_value.mdmcfg4));
                 // ui.label(format!("register_dr_m = {:08b}", self.register_value.mdmcfg3));
                 ui.end_row();
-                
-                ui.horizontal(|ui| {
-    
-                });  
-            });
-
-            if ui.button("Write Register").clicked() {
-                let write_register_frame = WriteRegisterFrame {
-                    address: self.register,
-                    value: self.value,
-                };
 
-                let packet = Packet {
-                    command_id: CommandID::WriteRegister,
-                    payload: postcard::to_allocvec(&write_register_frame).expect("Failed to serialize packet"),
-                };
+                ui.label("Modulation Index");
+                ui.vertical(|ui| {
+                    ui.label(format!("h = {}", format_value(self.register_value.modulation_index(self.crystal_hz))));
+                    ui.checkbox(&mut self.lock_modulation_index, "Keep h when the data rate changes");
+                    ui.checkbox(&mut self.lock_bandwidth_ratio, "Keep channel bandwidth ratio");
+                    if !self.locked_ratio_message.is_empty() {
+                        ui.colored_label(ui.visuals().warn_fg_color, &self.locked_ratio_message);
+                    }
+                });
+                ui.end_row();
 
-                self.send_message(&packet).expect("Failed to send message");
-            }
+                self.parameter_label(ui, "Channel Bandwidth");
+                ui.vertical(|ui| {
+                    let old_channel_bandwidth = self.channel_bandwidth();
+                    let old_chanbw = self.user_input_chanbw;
+                    egui::ComboBox::from_id_salt("channel_bandwidth")
+                        .selected_text(format_value(conversions::register_to_channel_bandwidth(self.user_input_chanbw >> 2, self.user_input_chanbw & 0x03, self.crystal_hz)))
+                        .show_ui(ui, |ui| {
+                            for (chanbw_e, chanbw_m, bandwidth) in conversions::channel_bandwidth_options(self.crystal_hz) {
+                                ui.selectable_value(&mut self.user_input_chanbw, chanbw_e << 2 | chanbw_m, format_value(bandwidth));
+                            }
+                    });
+                    if self.user_input_chanbw != old_chanbw {
+                        let before = self.register_value;
+                        self.update_channel_bandwidth_from_parameter();
+                        self.record_change(before, format!("Channel bandwidth {} → {} kHz", format_value(old_channel_bandwidth), format_value(self.channel_bandwidth())));
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut format_value(self.channel_bandwidth())).clip_text(true).desired_width(68.0)
+                    );
+                });
+                ui.label("kHz");
+                ui.end_row();
 
-            // Display received data
-            if !self.received_data.is_empty() {
-                ui.label(format!(
-                    "Received data: {:?}",
-                    String::from_utf8_lossy(&self.received_data)
-                ));
-            }
-        });
+                self.parameter_label(ui, "Channel Spacing");
+                ui.vertical(|ui| {
+                    let channel_spacing_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_channel_spacing).desired_width(68.0));
+                    if channel_spacing_text_box.lost_focus() {
+                        self.channel_spacing_input_is_out_of_bounds();
+                    }
+                    if self.invalid_channel_spacing_popup {
+                        self.show_invalid_channel_spacing_popup(ctx);
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut format_value(self.channel_spacing())).clip_text(true).desired_width(68.0)
+                    );
+                });
+                ui.label((self.register_value.mdmcfg1 & 0x03).to_string());
+                ui.label(self.register_value.mdmcfg0.to_string());
+                ui.label("kHz");
+                ui.end_row();
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            
+                ui.label("Channel Frequency");
+                let channel_frequency = self.channel_frequency();
+                if self.chip.frequency_in_band(channel_frequency) {
+                    ui.label(format_value(channel_frequency));
+                } else {
+                    ui.colored_label(ui.visuals().warn_fg_color, format!("{} (out of band)", format_value(channel_frequency)));
+                }
+                ui.label("MHz");
+                ui.end_row();
+
+                self.parameter_label(ui, "IF Frequency");
+                ui.vertical(|ui| {
+                    let if_frequency_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_if_frequency).desired_width(68.0));
+                    if if_frequency_text_box.lost_focus() {
+                        self.if_frequency_input_is_out_of_bounds();
+                    }
+                    if self.invalid_if_frequency_popup {
+                        self.show_invalid_if_frequency_popup(ctx);
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut format_value(self.if_frequency())).clip_text(true).desired_width(68.0)
+                    );
+                });
+                ui.label(format!("FSCTRL1 0x{:02X}", self.register_value.fsctrl1));
+                ui.label("kHz");
+                ui.end_row();
+
+                self.parameter_label(ui, "Frequency Offset");
+                ui.vertical(|ui| {
+                    let frequency_offset_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_frequency_offset).desired_width(68.0));
+                    if frequency_offset_text_box.lost_focus() {
+                        self.frequency_offset_input_is_out_of_bounds();
+                    }
+                    if self.invalid_frequency_offset_popup {
+                        self.show_invalid_frequency_offset_popup(ctx);
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut format_value(self.frequency_offset())).clip_text(true).desired_width(68.0)
+                    );
+                });
+                ui.label(format!("FSCTRL0 0x{:02X}", self.register_value.fsctrl0));
+                ui.label("kHz");
+                ui.end_row();
+
+                self.parameter_label(ui, "Sync Word");
+                ui.horizontal(|ui| {
+                    let sync_word_text_box = ui.add(egui::TextEdit::singleline(&mut self.user_input_sync_word).desired_width(68.0));
+                    if sync_word_text_box.lost_focus() {
+                        self.sync_word_input_is_out_of_bounds();
+                    }
+                    if self.invalid_sync_word_popup {
+                        self.show_invalid_sync_word_popup(ctx);
+                    }
+                });
+                ui.label(format!("SYNC1 0x{:02X}", self.register_value.sync1));
+                ui.label(format!("SYNC0 0x{:02X}", self.register_value.sync0));
+                ui.end_row();
+
+                self.parameter_label(ui, "Sync Mode");
+                ui.horizontal(|ui| {
+                    let old_sync_mode = self.user_input_sync_mode;
+                    egui::ComboBox::from_id_salt("sync_mode")
+                        .selected_text(field_options::SYNC_MODES[self.user_input_sync_mode as usize])
+                        .width(220.0)
+                        .show_ui(ui, |ui| {
+                            for (sync_mode, name) in field_options::SYNC_MODES.iter().enumerate() {
+                                ui.selectable_value(&mut self.user_input_sync_mode, sync_mode as u8, *name);
+                            }
+                    });
+                    if self.user_input_sync_mode != old_sync_mode {
+                        let before = self.register_value;
+                        self.update_sync_mode_from_parameter();
+                        self.record_change(before, format!("Sync mode {} → {}", field_options::SYNC_MODES[old_sync_mode as usize], field_options::SYNC_MODES[self.user_input_sync_mode as usize]));
+                    }
+                });
+                ui.label(format!("MDMCFG2 {:08b}", self.register_value.mdmcfg2));
+                ui.end_row();
+
+                self.parameter_label(ui, "Preamble");
+                ui.horizontal(|ui| {
+                    let old_num_preamble = self.user_input_num_preamble;
+                    egui::ComboBox::from_id_salt("num_preamble")
+                        .selected_text(format!("{} bytes", field_options::PREAMBLE_BYTES[self.user_input_num_preamble as usize]))
+                        .show_ui(ui, |ui| {
+                            for (num_preamble, bytes) in field_options::PREAMBLE_BYTES.iter().enumerate() {
+                                ui.selectable_value(&mut self.user_input_num_preamble, num_preamble as u8, format!("{} bytes", bytes));
+                            }
+                    });
+                    if self.user_input_num_preamble != old_num_preamble {
+                        let before = self.register_value;
+                        self.update_num_preamble_from_parameter();
+                        self.record_change(before, format!("Preamble {} → {} bytes", field_options::PREAMBLE_BYTES[old_num_preamble as usize], field_options::PREAMBLE_BYTES[self.user_input_num_preamble as usize]));
+                    }
+                });
+                ui.label(format!("MDMCFG1 {:08b}", self.register_value.mdmcfg1));
+                ui.end_row();
+
+                self.parameter_label(ui, "Packet Length Mode");
+                ui.horizontal(|ui| {
+                    let old_length_config = self.user_input_length_config;
+                    egui::ComboBox::from_id_salt("length_config")
+                        .selected_text(field_options::LENGTH_CONFIGS[self.user_input_length_config as usize])
+                        .width(220.0)
+                        .show_ui(ui, |ui| {
+                            for (length_config, name) in field_options::LENGTH_CONFIGS.iter().enumerate().take(3) {
+                                ui.selectable_value(&mut self.user_input_length_config, length_config as u8, *name);
+                            }
+                    });
+                    if self.user_input_length_config != old_length_config {
+                        let before = self.register_value;
+                        self.update_length_config_from_parameter();
+                        self.record_change(before, format!("Packet length mode {} → {}", field_options::LENGTH_CONFIGS[old_length_config as usize], field_options::LENGTH_CONFIGS[self.user_input_length_config as usize]));
+                    }
+                });
+                ui.label(format!("PKTCTRL0 {:08b}", self.register_value.pktctrl0));
+                ui.end_row();
+
+                self.parameter_label(ui, "Packet Length");
+                ui.horizontal(|ui| {
+                    if ui.add(egui::DragValue::new(&mut self.user_input_packet_length).range(0..=255)).changed() {
+                        let before = self.register_value;
+                        self.update_packet_length_from_parameter();
+                        self.record_change(before, format!("Packet length {} → {}", before.pktlen, self.register_value.pktlen));
+                    }
+                });
+                ui.label(format!("PKTLEN 0x{:02X}", self.register_value.pktlen));
+                ui.end_row();
+
+                self.parameter_label(ui, "CRC");
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut self.crc_enabled, "CRC Enable").clicked() {
+                        let before = self.register_value;
+                        self.update_crc_from_parameter();
+                        self.record_change(before, format!("CRC {}", if self.crc_enabled {"off → on"} else {"on → off"}));
+                    }
+                });
+                ui.label(format!("PKTCTRL0 {:08b}", self.register_value.pktctrl0));
+                ui.end_row();
+
+                self.parameter_label(ui, "Append Status");
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut self.append_status_enabled, "RSSI/LQI after payload").clicked() {
+                        let before = self.register_value;
+                        self.update_append_status_from_parameter();
+                        self.record_change(before, format!("Append status {}", if self.append_status_enabled {"off → on"} else {"on → off"}));
+                    }
+                });
+                ui.label(format!("PKTCTRL1 {:08b}", self.register_value.pktctrl1));
+                ui.end_row();
+
+                self.parameter_label(ui, "Address Check");
+                ui.horizontal(|ui| {
+                    let old_address_check = self.user_input_address_check;
+                    egui::ComboBox::from_id_salt("address_check")
+                        .selected_text(field_options::ADDRESS_CHECK_MODES[self.user_input_address_check as usize])
+                        .width(220.0)
+                        .show_ui(ui, |ui| {
+                            for (address_check, name) in field_options::ADDRESS_CHECK_MODES.iter().enumerate() {
+                                ui.selectable_value(&mut self.user_input_address_check, address_check as u8, *name);
+                            }
+                    });
+                    if self.user_input_address_check != old_address_check {
+                        let before = self.register_value;
+                        self.update_address_check_from_parameter();
+                        self.record_change(before, format!("Address check {} → {}", field_options::ADDRESS_CHECK_MODES[old_address_check as usize], field_options::ADDRESS_CHECK_MODES[self.user_input_address_check as usize]));
+                    }
+                });
+                ui.label(format!("PKTCTRL1 {:08b}", self.register_value.pktctrl1));
+                ui.end_row();
+
+                self.parameter_label(ui, "Device Address");
+                ui.horizontal(|ui| {
+                    if ui.add(egui::DragValue::new(&mut self.user_input_device_address).range(0..=255).hexadecimal(2, false, true)).changed() {
+                        let before = self.register_value;
+                        self.update_device_address_from_parameter();
+                        self.record_change(before, format!("Device address 0x{:02X} → 0x{:02X}", before.addr, self.register_value.addr));
+                    }
+                });
+                ui.label(format!("ADDR 0x{:02X}", self.register_value.addr));
+                ui.end_row();
+
+                self.parameter_label(ui, "Preamble Quality Threshold");
+                ui.horizontal(|ui| {
+                    let old_pqt = self.user_input_pqt;
+                    egui::ComboBox::from_id_salt("pqt")
+                        .selected_text(if self.user_input_pqt == 0 {"Off".to_string()} else {(4 * self.user_input_pqt).to_string()})
+                        .show_ui(ui, |ui| {
+                            for pqt in 0..8u8 {
+                                ui.selectable_value(&mut self.user_input_pqt, pqt, if pqt == 0 {"Off".to_string()} else {(4 * pqt).to_string()});
+                            }
+                    });
+                    if self.user_input_pqt != old_pqt {
+                        let before = self.register_value;
+                        self.update_pqt_from_parameter();
+                        self.record_change(before, format!("Preamble quality threshold {} → {}", 4 * old_pqt, 4 * self.user_input_pqt));
+                    }
+                });
+                ui.label(format!("PKTCTRL1 {:08b}", self.register_value.pktctrl1));
+                ui.end_row();
+            });
+    }
+
+    fn frequency_input_is_out_of_bounds(&mut self) {
+        if let Ok(value) = self.user_input_frequency.trim().parse::<f64>() {
+            // Check if the value is out of bounds
+            if !self.chip.frequency_in_band(value) {
+                self.invalid_frequency_popup = true; // Trigger the popup
+            } else {
+                let before = self.register_value;
+                let old_frequency = self.base_frequency();
+                self.update_base_frequency_from_parameter();
+                self.record_change(before, format!("Base frequency {} → {} MHz", format_frequency(old_frequency), format_frequency(self.base_frequency())));
+                self.invalid_frequency_popup = false;
+            }
+        } else {
+            // Show popup for invalid input
+            self.invalid_frequency_popup = true;
+        } 
+    }
+
+    fn show_invalid_frequency_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Frequency Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("The base frequency for the {} must be between {}!", self.chip.name, self.chip.bands_text()));
+                if ui.button("OK").clicked() {
+                    self.invalid_frequency_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn deviation_input_is_out_of_bounds(&mut self) {
+        let deviation_min = conversions::register_to_deviation(0, 0, self.crystal_hz);
+        let deviation_max = conversions::register_to_deviation(7, 7, self.crystal_hz);
+        if let Ok(value) = self.user_input_deviation.trim().parse::<f64>() {
+            // Check if the value is out of bounds
+            if !(deviation_min..=deviation_max).contains(&value) {
+                self.invalid_deviation_popup = true; // Trigger the popup
+            } else {
+                let before = self.register_value;
+                let old_deviation = self.deviation();
+                self.update_deviation_from_parameter();
+                self.record_change(before, format!("Deviation {} → {} kHz", format_value(old_deviation), format_value(self.deviation())));
+                self.invalid_deviation_popup = false;
+            }
+        } else {
+            // Show popup for invalid input
+            self.invalid_deviation_popup = true;
+        }
+    }
+
+    fn show_invalid_deviation_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Deviation Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("The deviation must be between {} and {} kHz!",
+                    format_value(conversions::register_to_deviation(0, 0, self.crystal_hz)),
+                    format_value(conversions::register_to_deviation(7, 7, self.crystal_hz))));
+                if ui.button("OK").clicked() {
+                    self.invalid_deviation_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn dr_input_is_out_of_bounds(&mut self) {
+        let dr_min = conversions::register_to_data_rate(0, 0, self.crystal_hz);
+        let dr_max = conversions::register_to_data_rate(15, 255, self.crystal_hz);
+        if let Ok(value) = self.user_input_dr.trim().parse::<f64>() {
+            // Check if the value is out of bounds
+            if !(dr_min..=dr_max).contains(&value) {
+                self.invalid_dr_popup = true; // Trigger the popup
+            } else {
+                let before = self.register_value;
+                let old_dr = self.dr();
+                self.update_dr_from_parameter();
+                let mut description = format!("Data rate {} → {} kBaud", format_value(old_dr), format_value(self.dr()));
+                for change in self.keep_locked_ratios(before) {
+                    description.push_str(", ");
+                    description.push_str(&change);
+                }
+                self.record_change(before, description);
+                self.invalid_dr_popup = false;
+            }
+        } else {
+            // Show popup for invalid input
+            self.invalid_dr_popup = true;
+        }
+    }
+
+    // Re-solves DEVIATN and CHANBW after a data rate change so the ratios they had in `before` stay the same.
+    // MSK already ties the deviation to the data rate and ASK/OOK has none, so only 2-FSK and GFSK are adjusted.
+    // Returns the changes for the history entry, targets outside the register range are clamped and reported
+    // in locked_ratio_message
+    fn keep_locked_ratios(&mut self, before: structs::RegisterValue) -> Vec<String> {
+        let mut changes = Vec::new();
+        let mut clamped = Vec::new();
+        let mod_format = (self.register_value.mdmcfg2 & 0x70) >> 4;
+        if self.lock_modulation_index && (mod_format == 0 || mod_format == 1) {
+            let deviation_min = conversions::register_to_deviation(0, 0, self.crystal_hz);
+            let deviation_max = conversions::register_to_deviation(7, 7, self.crystal_hz);
+            let deviation = before.modulation_index(self.crystal_hz) * self.dr() / 2.0;
+            if !(deviation_min..=deviation_max).contains(&deviation) {
+                clamped.push(format!("h needs {} kHz deviation", format_value(deviation)));
+            }
+            self.set_deviation(deviation.clamp(deviation_min, deviation_max));
+            self.user_input_deviation = format_value(self.deviation());
+            if self.register_value.deviatn != before.deviatn {
+                changes.push(format!("deviation {} → {} kHz", format_value(before.deviation(self.crystal_hz)), format_value(self.deviation())));
+            }
+        }
+        if self.lock_bandwidth_ratio {
+            let old_signal_bandwidth = conversions::signal_bandwidth(before.data_rate(self.crystal_hz), before.effective_deviation(self.crystal_hz));
+            let ratio = before.channel_bandwidth(self.crystal_hz) / old_signal_bandwidth;
+            let bandwidth = ratio * conversions::signal_bandwidth(self.dr(), self.register_value.effective_deviation(self.crystal_hz));
+            let bandwidth_min = conversions::register_to_channel_bandwidth(3, 3, self.crystal_hz);
+            let bandwidth_max = conversions::register_to_channel_bandwidth(0, 0, self.crystal_hz);
+            if !(bandwidth_min..=bandwidth_max).contains(&bandwidth) {
+                clamped.push(format!("the bandwidth ratio needs a {} kHz channel filter", format_value(bandwidth)));
+            }
+            let options = conversions::channel_bandwidth_options(self.crystal_hz);
+            if let Some((chanbw_e, chanbw_m, _)) = options.into_iter().min_by(|(_, _, a), (_, _, b)| (a - bandwidth).abs().total_cmp(&(b - bandwidth).abs())) {
+                self.user_input_chanbw = chanbw_e << 2 | chanbw_m;
+                self.update_channel_bandwidth_from_parameter();
+            }
+            if self.register_value.mdmcfg4 >> 4 != before.mdmcfg4 >> 4 {
+                changes.push(format!("channel bandwidth {} → {} kHz", format_value(before.channel_bandwidth(self.crystal_hz)), format_value(self.channel_bandwidth())));
+            }
+        }
+        self.locked_ratio_message = if clamped.is_empty() {
+            String::new()
+        } else {
+            format!("Out of range, {}, the closest setting is used", clamped.join(" and "))
+        };
+        changes
+    }
+
+    // Channels are only applied if the resulting center frequency stays inside the band
+    fn channel_number_is_out_of_bounds(&mut self) {
+        let frequency = conversions::channel_frequency(self.base_frequency(), self.user_input_channel_number, self.channel_spacing());
+        if !self.chip.frequency_in_band(frequency) {
+            self.user_input_channel_number = self.register_value.channr;
+            self.invalid_channel_frequency_popup = true;
+        } else {
+            let before = self.register_value;
+            self.update_channel_number_from_parameter();
+            self.record_change(before, format!("Channel number {} → {}", before.channr, self.register_value.channr));
+        }
+    }
+
+    fn channel_spacing_input_is_out_of_bounds(&mut self) {
+        let spacing_min = conversions::register_to_channel_spacing(0, 0, self.crystal_hz);
+        let spacing_max = conversions::register_to_channel_spacing(3, 255, self.crystal_hz);
+        if let Ok(value) = self.user_input_channel_spacing.trim().parse::<f64>() {
+            // Check if the value is out of bounds
+            if !(spacing_min..=spacing_max).contains(&value) {
+                self.invalid_channel_spacing_popup = true; // Trigger the popup
+            } else if !self.chip.frequency_in_band(conversions::channel_frequency(self.base_frequency(), self.register_value.channr, value)) {
+                self.invalid_channel_frequency_popup = true;
+            } else {
+                let before = self.register_value;
+                let old_channel_spacing = self.channel_spacing();
+                self.update_channel_spacing_from_parameter();
+                self.record_change(before, format!("Channel spacing {} → {} kHz", format_value(old_channel_spacing), format_value(self.channel_spacing())));
+                self.invalid_channel_spacing_popup = false;
+            }
+        } else {
+            // Show popup for invalid input
+            self.invalid_channel_spacing_popup = true;
+        }
+    }
+
+    fn show_invalid_channel_spacing_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Channel Spacing Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("The channel spacing must be between {} and {} kHz!",
+                    format_value(conversions::register_to_channel_spacing(0, 0, self.crystal_hz)),
+                    format_value(conversions::register_to_channel_spacing(3, 255, self.crystal_hz))));
+                if ui.button("OK").clicked() {
+                    self.invalid_channel_spacing_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn show_invalid_channel_frequency_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Channel")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("The channel center frequency for the {} must be between {}!", self.chip.name, self.chip.bands_text()));
+                if ui.button("OK").clicked() {
+                    self.invalid_channel_frequency_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn sync_word_input_is_out_of_bounds(&mut self) {
+        if parse_sync_word(&self.user_input_sync_word).is_some() {
+            let before = self.register_value;
+            self.update_sync_word_from_parameter();
+            self.record_change(before, format!("Sync word 0x{:02X}{:02X} → 0x{:02X}{:02X}", before.sync1, before.sync0, self.register_value.sync1, self.register_value.sync0));
+            self.invalid_sync_word_popup = false;
+        } else {
+            // Show popup for invalid input
+            self.invalid_sync_word_popup = true;
+        }
+    }
+
+    fn show_invalid_sync_word_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Sync Word Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("The sync word must be exactly 4 hex digits, e.g. D391!");
+                if ui.button("OK").clicked() {
+                    self.invalid_sync_word_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn if_frequency_input_is_out_of_bounds(&mut self) {
+        let if_frequency_max = conversions::register_to_if_frequency(31, self.crystal_hz);
+        if let Ok(value) = self.user_input_if_frequency.trim().parse::<f64>() {
+            // Check if the value is out of bounds
+            if !(0.0..=if_frequency_max).contains(&value) {
+                self.invalid_if_frequency_popup = true; // Trigger the popup
+            } else {
+                let before = self.register_value;
+                let old_if_frequency = self.if_frequency();
+                self.update_if_frequency_from_parameter();
+                self.record_change(before, format!("IF frequency {} → {} kHz", format_value(old_if_frequency), format_value(self.if_frequency())));
+                self.invalid_if_frequency_popup = false;
+            }
+        } else {
+            // Show popup for invalid input
+            self.invalid_if_frequency_popup = true;
+        }
+    }
+
+    fn show_invalid_if_frequency_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid IF Frequency Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("The IF frequency must be between 0 and {} kHz!", format_value(conversions::register_to_if_frequency(31, self.crystal_hz))));
+                if ui.button("OK").clicked() {
+                    self.invalid_if_frequency_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn frequency_offset_input_is_out_of_bounds(&mut self) {
+        let offset_min = conversions::register_to_frequency_offset(0x80, self.crystal_hz);
+        let offset_max = conversions::register_to_frequency_offset(0x7F, self.crystal_hz);
+        if let Ok(value) = self.user_input_frequency_offset.trim().parse::<f64>() {
+            // Check if the value is out of bounds
+            if !(offset_min..=offset_max).contains(&value) {
+                self.invalid_frequency_offset_popup = true; // Trigger the popup
+            } else {
+                let before = self.register_value;
+                let old_frequency_offset = self.frequency_offset();
+                self.update_frequency_offset_from_parameter();
+                self.record_change(before, format!("Frequency offset {} → {} kHz", format_value(old_frequency_offset), format_value(self.frequency_offset())));
+                self.invalid_frequency_offset_popup = false;
+            }
+        } else {
+            // Show popup for invalid input
+            self.invalid_frequency_offset_popup = true;
+        }
+    }
+
+    fn show_invalid_frequency_offset_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Frequency Offset Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("The frequency offset must be between {} and {} kHz!",
+                    format_value(conversions::register_to_frequency_offset(0x80, self.crystal_hz)),
+                    format_value(conversions::register_to_frequency_offset(0x7F, self.crystal_hz))));
+                if ui.button("OK").clicked() {
+                    self.invalid_frequency_offset_popup = false; // Close the popup
+                }
+        });
+    }
+
+    fn show_invalid_dr_popup(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Invalid Data Rate Input")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui: &mut egui::Ui| {
+                ui.label(format!("The data rate must be between {} and {} kBaud!",
+                    format_value(conversions::register_to_data_rate(0, 0, self.crystal_hz)),
+                    format_value(conversions::register_to_data_rate(15, 255, self.crystal_hz))));
+                if ui.button("OK").clicked() {
+                    self.invalid_dr_popup = false; // Close the popup
+                }
+        });
+    }
+}
+
+// implementation of the UI for SerialApp
+impl eframe::App for SerialApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Check for received data
+        while let Ok(byte) = self.rx.try_recv() {
+            match self.pending_reads.pop_front() {
+                Some(pending) => self.handle_register_read(pending, byte),
+                None => self.received_data.push(byte),
+            }
+        }
+
+        self.handle_history_shortcuts(ctx);
+        self.poll_status_registers(ctx);
+
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            self.show_tab_bar(ui);
+            ui.horizontal(|ui| {
+                ui.label("Configuration file");
+                ui.add(egui::TextEdit::singleline(&mut self.config_file_path).desired_width(250.0));
+                if ui.button("Save").clicked() {
+                    self.save_configuration();
+                }
+                if ui.button("Load").clicked() {
+                    self.load_configuration();
+                }
+                if ui.button("Read Radio").clicked() {
+                    self.read_radio_registers();
+                }
+                ui.label(&self.config_file_message);
+                ui.separator();
+                if ui.button("Compare...").clicked() {
+                    self.diff.open = true;
+                }
+                if ui.button("Channel Solver...").clicked() {
+                    self.channel_solver.open = true;
+                }
+                if ui.button("Channel Table...").clicked() {
+                    self.channel_table.open = true;
+                }
+                if ui.button("State Machine...").clicked() {
+                    self.state_machine_view.open = true;
+                }
+                if ui.button("AGC...").clicked() {
+                    self.agc_view.open = true;
+                }
+                if ui.button("Offset Compensation...").clicked() {
+                    self.foc_view.open = true;
+                }
+                if ui.button("GPIO...").clicked() {
+                    self.gpio_view.open = true;
+                }
+                if ui.button("PA Table...").clicked() {
+                    self.pa_table_view.open = true;
+                }
+                if ui.button("Link Budget...").clicked() {
+                    self.link_budget.open = true;
+                }
+                if ui.button("Formulas...").clicked() {
+                    self.formula_view.open = true;
+                }
+            });
+        });
+
+        if self.diff.open {
+            self.show_diff_window(ctx);
+        }
+        if self.channel_solver.open {
+            self.show_channel_solver_window(ctx);
+        }
+        if self.state_machine_view.open {
+            self.show_state_machine_window(ctx);
+        }
+        if self.agc_view.open {
+            self.show_agc_window(ctx);
+        }
+        if self.foc_view.open {
+            self.show_foc_window(ctx);
+        }
+        if self.gpio_view.open {
+            self.show_gpio_window(ctx);
+        }
+        if self.pa_table_view.open {
+            self.show_pa_table_window(ctx);
+        }
+        if self.link_budget.open {
+            self.show_link_budget_window(ctx);
+        }
+        if self.channel_table.open {
+            self.show_channel_table_window(ctx);
+        }
+        if self.formula_view.open {
+            self.show_formula_window(ctx);
+        }
+
+        egui::SidePanel::left("left_panel").show(ctx, |ui| {
+            egui::ScrollArea::vertical().max_height(ui.available_height() - 60.0).show(ui, |ui| {
+                self.show_parameters_grid(ctx, ui);
+            });
+
+            if ui.button("Write Register").clicked() {
+                let write_register_frame = WriteRegisterFrame {
+                    address: self.register,
+                    value: self.value,
+                };
+
+                let packet = Packet {
+                    command_id: CommandID::WriteRegister,
+                    payload: postcard::to_allocvec(&write_register_frame).expect("Failed to serialize packet"),
+                };
+
+                self.send_message(&packet).expect("Failed to send message");
+            }
+
+            // Display received data
+            if !self.received_data.is_empty() {
+                ui.label(format!(
+                    "Received data: {:?}",
+                    String::from_utf8_lossy(&self.received_data)
+                ));
+            }
         });
 
         egui::SidePanel::right("right_panel").show(ctx, |ui| {
             egui::Grid::new("right_panel")
                 .striped(true)
                 .show(ui, |ui| {
-                    let mut toggle = toggle_ui(ui, &mut self.is_hex);
+                    ui.label("Chip");
+                    egui::ComboBox::from_id_salt("chip_select")
+                        .selected_text(self.chip.name)
+                        .show_ui(ui, |ui| {
+                            for chip in chips::ALL_CHIPS {
+                                if ui.selectable_label(std::ptr::eq(self.chip, chip), chip.name).clicked() {
+                                    self.select_chip(chip);
+                                    self.chip_detect_message.clear();
+                                }
+                            }
+                    });
+                    if ui.button("Auto-detect").clicked() {
+                        self.detect_chip();
+                    }
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.label(&self.chip_detect_message);
+                    ui.end_row();
+
+                    ui.label("Crystal");
+                    ui.horizontal(|ui| {
+                        let mut crystal_khz = self.crystal_hz / 1000;
+                        if ui.add(egui::DragValue::new(&mut crystal_khz).speed(10.0).range(config_file::CRYSTAL_HZ_RANGE.start() / 1000..=config_file::CRYSTAL_HZ_RANGE.end() / 1000).suffix(" kHz")).changed() {
+                            self.set_crystal(crystal_khz * 1000);
+                        }
+                        for crystal_mhz in [24, 26, 27] {
+                            if ui.small_button(format!("{}", crystal_mhz)).clicked() {
+                                self.set_crystal(crystal_mhz * 1_000_000);
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Bands");
+                    ui.label(format!("{} MHz", self.chip.bands_text()));
+                    ui.end_row();
+
+                    ui.label("Features");
+                    ui.label(format!("USB: {}, ASK/OOK: {}",
+                        if self.chip.has_usb {"yes"} else {"no"},
+                        if self.chip.supports_ask_ook {"yes"} else {"no"}));
+                    ui.end_row();
+
+                    toggle_ui(ui, &mut self.is_hex);
                 });
+            ui.separator();
+            self.show_history_panel(ui);
+        });
+
+        egui::TopBottomPanel::bottom("warnings_panel").show(ctx, |ui| {
+            self.show_warnings_panel(ui);
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.show_status_monitor(ui);
         });
     }
 }
@@ -652,6 +1788,47 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
+// Rounds to 3 decimals and drops trailing zeros, so values read "250" rather than "250.000"
+fn format_value(value: f64) -> String {
+    let formatted = format!("{:.3}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// MHz down to 1 Hz, a FREQ step is only ~400 Hz so format_value's 1 kHz would land on a neighbouring FREQ
+fn format_frequency(frequency_mhz: f64) -> String {
+    let formatted = format!("{:.6}", frequency_mhz);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// Exactly 4 hex digits with an optional 0x, from_str_radix alone would take "1" or "+D391"
+fn parse_sync_word(text: &str) -> Option<u16> {
+    let text = text.trim();
+    let digits = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text);
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u16::from_str_radix(digits, 16).ok()
+}
+
+fn tx_power_text(tx_power: Option<i8>) -> String {
+    match tx_power {
+        Some(dbm) => dbm.to_string(),
+        None => "custom".to_string(),
+    }
+}
+
+// Error of the encoded value against the typed one, `scale` converts the input unit to `unit`.
+// Nothing is shown once the value changed some other way (undo, loading, another crystal)
+fn conversion_error_text(requested: Option<RequestedValue>, current: f64, scale: f64, unit: &str) -> String {
+    match requested {
+        Some(requested) if requested.target.is_finite() && requested.achieved == current => {
+            let (error, ppm) = conversions::conversion_error(requested.target, requested.achieved);
+            format!("error {:+.1} {} ({:+.1} ppm)", error * scale, unit, ppm)
+        }
+        _ => String::new(),
+    }
+}
+
 fn toggle_ui(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
     let desired_size = ui.spacing().interact_size.y * egui::vec2(2.0, 1.0);
     let (rect, mut response) = ui.allocate_exact_size(desired_size, egui::Sense::click());
@@ -678,3 +1855,31 @@ fn toggle_ui(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{XOSC_24_MHZ, XOSC_26_MHZ, XOSC_27_MHZ};
+
+    #[test]
+    fn frequency_text_round_trips_to_the_same_freq() {
+        for crystal_hz in [XOSC_24_MHZ, XOSC_26_MHZ, XOSC_27_MHZ] {
+            for freq in 0x5EC4EC..=0x5EC4EF {
+                let text = format_frequency(conversions::register_to_frequency(freq, crystal_hz));
+                let frequency = text.parse::<f64>().unwrap();
+                assert_eq!(conversions::frequency_to_register(frequency, crystal_hz), freq, "{} at {} Hz", text, crystal_hz);
+            }
+        }
+    }
+
+    #[test]
+    fn sync_word_needs_four_hex_digits() {
+        assert_eq!(parse_sync_word("D391"), Some(0xD391));
+        assert_eq!(parse_sync_word(" 0xd391 "), Some(0xD391));
+        assert_eq!(parse_sync_word("0X00FF"), Some(0x00FF));
+        assert_eq!(parse_sync_word("1"), None);
+        assert_eq!(parse_sync_word("+D391"), None);
+        assert_eq!(parse_sync_word("D3911"), None);
+        assert_eq!(parse_sync_word("0x"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use eframe::egui;
+use crate::SerialApp;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RampShape {
+    Linear,
+    RaisedCosine,
+}
+
+impl RampShape {
+    fn name(self) -> &'static str {
+        match self {
+            RampShape::Linear => "Linear",
+            RampShape::RaisedCosine => "Raised cosine",
+        }
+    }
+
+    // Relative amplitude at `position` from 0.0 (PA off) to 1.0 (full power)
+    fn amplitude(self, position: f64) -> f64 {
+        match self {
+            RampShape::Linear => position,
+            RampShape::RaisedCosine => (1.0 - (position * std::f64::consts::PI).cos()) / 2.0,
+        }
+    }
+}
+
+pub struct PaTableView {
+    pub open: bool,
+    ramp_shape: RampShape,
+    ramp_target_dbm: i8,
+}
+
+impl PaTableView {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            ramp_shape: RampShape::RaisedCosine,
+            ramp_target_dbm: 0,
+        }
+    }
+}
+
+const PA_TABLE_NAMES: [&str; 8] = ["PA_TABLE0", "PA_TABLE1", "PA_TABLE2", "PA_TABLE3", "PA_TABLE4", "PA_TABLE5", "PA_TABLE6", "PA_TABLE7"];
+
+impl SerialApp {
+    // Fills PA_TABLE0 up to the PA_POWER entry with an amplitude ramp from off to the target power,
+    // the PA steps through these entries when a 1 starts and back down when it ends
+    fn fill_pa_ramp(&mut self) {
+        let before = self.register_value;
+        let steps = self.register_value.pa_power();
+        let target_dbm = self.pa_table_view.ramp_target_dbm as f64;
+        let frequency = self.channel_frequency();
+        *self.register_value.pa_table_entry_mut(0) = 0x00;
+        for index in 1..=steps {
+            let amplitude = self.pa_table_view.ramp_shape.amplitude(index as f64 / steps as f64);
+            let setting = self.chip.nearest_pa_setting(target_dbm + 20.0 * amplitude.log10(), frequency);
+            *self.register_value.pa_table_entry_mut(index) = setting.value;
+        }
+        self.record_change(before, format!("{} PA ramp to {} dBm over {} steps", self.pa_table_view.ramp_shape.name(), self.pa_table_view.ramp_target_dbm, steps));
+        self.update_parameters_from_registers();
+    }
+
+    pub(crate) fn show_pa_table_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.pa_table_view.open;
+        egui::Window::new("PA Table")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let is_ook = self.register_value.mdmcfg2 >> 4 & 0x07 == 3;
+                egui::Grid::new("pa_power")
+                    .show(ui, |ui| {
+                        self.register_field_combo(ui, "FREND0", "PA_POWER", &PA_TABLE_NAMES, 0, 0x07);
+                    });
+                if is_ook && self.register_value.pa_power() == 0 {
+                    ui.colored_label(ui.visuals().warn_fg_color, "With ASK/OOK PA_POWER 0 sends 0s and 1s with the same power");
+                }
+                ui.separator();
+
+                egui::Grid::new("pa_table_entries")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        let pa_power = self.register_value.pa_power();
+                        let frequency = self.channel_frequency();
+                        for (index, name) in PA_TABLE_NAMES.iter().enumerate() {
+                            let index = index as u8;
+                            ui.label(*name);
+                            let mut value = self.register_value.pa_table()[index as usize];
+                            if ui.add(egui::DragValue::new(&mut value).range(0..=255).hexadecimal(2, false, true)).changed() {
+                                let before = self.register_value;
+                                *self.register_value.pa_table_entry_mut(index) = value;
+                                self.record_change(before, format!("{} 0x{:02X} → 0x{:02X}", name, before.pa_table()[index as usize], value));
+                                self.update_parameters_from_registers();
+                            }
+                            ui.label(self.chip.estimate_pa_setting(value, frequency).text());
+                            ui.label(match (is_ook, index) {
+                                (true, 0) => "sent for a 0",
+                                (true, _) if index == pa_power => "sent for a 1",
+                                (false, _) if index == pa_power => "TX output",
+                                (_, _) if index < pa_power => "ramp",
+                                _ => "",
+                            });
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+
+                ui.strong("Ramp");
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("ramp_shape")
+                        .selected_text(self.pa_table_view.ramp_shape.name())
+                        .show_ui(ui, |ui| {
+                            for shape in [RampShape::Linear, RampShape::RaisedCosine] {
+                                ui.selectable_value(&mut self.pa_table_view.ramp_shape, shape, shape.name());
+                            }
+                    });
+                    ui.label("to");
+                    egui::ComboBox::from_id_salt("ramp_target")
+                        .selected_text(format!("{} dBm", self.pa_table_view.ramp_target_dbm))
+                        .show_ui(ui, |ui| {
+                            for setting in self.chip.pa_settings(self.channel_frequency()) {
+                                ui.selectable_value(&mut self.pa_table_view.ramp_target_dbm, setting.dbm, format!("{} dBm", setting.dbm));
+                            }
+                    });
+                    let can_ramp = self.register_value.pa_power() > 0;
+                    if ui.add_enabled(can_ramp, egui::Button::new("Fill")).on_disabled_hover_text("Set PA_POWER above 0 to ramp").clicked() {
+                        self.fill_pa_ramp();
+                    }
+                });
+            });
+        self.pa_table_view.open = open;
+    }
+}
@@ -0,0 +1,108 @@
+use eframe::egui;
+use crate::{field_options, SerialApp};
+
+pub struct StateMachineView {
+    pub open: bool,
+}
+
+impl StateMachineView {
+    pub fn new() -> Self {
+        Self { open: false }
+    }
+}
+
+impl SerialApp {
+    pub(crate) fn show_state_machine_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.state_machine_view.open;
+        egui::Window::new("Radio Control State Machine")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("mcsm_fields")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.register_field_combo(ui, "MCSM1", "After RX", &field_options::RXOFF_MODES, 2, 0x03);
+                        self.register_field_combo(ui, "MCSM1", "After TX", &field_options::TXOFF_MODES, 0, 0x03);
+                        self.register_field_combo(ui, "MCSM1", "Clear channel", &field_options::CCA_MODES, 4, 0x03);
+                        self.register_field_combo(ui, "MCSM0", "Auto calibrate", &field_options::FS_AUTOCAL_MODES, 4, 0x03);
+                        self.register_field_combo(ui, "MCSM2", "RX timeout", &field_options::RX_TIMES, 0, 0x07);
+                        self.register_field_combo(ui, "MCSM2", "RX timeout qualifier", &["Sync word found", "Sync word or PQI reached"], 3, 0x01);
+                        self.register_field_combo(ui, "MCSM2", "End RX on low RSSI", &["Off", "On"], 4, 0x01);
+                    });
+                ui.separator();
+                self.draw_state_diagram(ui);
+            });
+        self.state_machine_view.open = open;
+    }
+
+    // IDLE on the left, FSTXON in the middle, RX above and TX below, with the arrows MCSM1 and MCSM0 select
+    fn draw_state_diagram(&self, ui: &mut egui::Ui) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(420.0, 220.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        let stroke = egui::Stroke::new(1.5, visuals.text_color());
+        let highlight = egui::Stroke::new(2.0, visuals.selection.bg_fill);
+        let font = egui::FontId::proportional(13.0);
+
+        let idle = rect.left_center() + egui::vec2(50.0, 0.0);
+        let fstxon = rect.center();
+        let rx = rect.right_top() + egui::vec2(-60.0, 35.0);
+        let tx = rect.right_bottom() + egui::vec2(-60.0, -35.0);
+        let node_size = egui::vec2(80.0, 28.0);
+
+        for (position, name) in [(idle, "IDLE"), (fstxon, "FSTXON"), (rx, "RX"), (tx, "TX")] {
+            painter.rect(egui::Rect::from_center_size(position, node_size), 6.0, visuals.extreme_bg_color, stroke);
+            painter.text(position, egui::Align2::CENTER_CENTER, name, font.clone(), visuals.text_color());
+        }
+
+        // edges stop at the node border so the arrow heads stay visible
+        let arrow = |from: egui::Pos2, to: egui::Pos2, stroke: egui::Stroke, label: &str| {
+            let direction = (to - from).normalized();
+            let start = from + direction * 30.0;
+            let end = to - direction * 30.0;
+            painter.arrow(start, end - start, stroke);
+            painter.text(start + (end - start) * 0.5 + egui::vec2(0.0, -10.0), egui::Align2::CENTER_CENTER, label, egui::FontId::proportional(11.0), ui.visuals().weak_text_color());
+        };
+
+        let autocal = (self.register_value.mcsm0 >> 4) & 0x03;
+        let calibrate_label = if autocal == 1 {"STX/SRX + CAL"} else {"STX/SRX"};
+        arrow(idle, rx, stroke, calibrate_label);
+        arrow(idle, tx, stroke, calibrate_label);
+
+        let rxoff_mode = (self.register_value.mcsm1 >> 2) & 0x03;
+        let after_rx = match rxoff_mode {
+            0 => Some(idle),
+            1 => Some(fstxon),
+            2 => Some(tx),
+            _ => None,
+        };
+        match after_rx {
+            Some(target) => arrow(rx + egui::vec2(-10.0, 0.0), target + egui::vec2(-10.0, 0.0), highlight, "RX done"),
+            None => {
+                painter.circle_stroke(rx + egui::vec2(48.0, -10.0), 10.0, highlight);
+                painter.text(rx + egui::vec2(0.0, -24.0), egui::Align2::CENTER_CENTER, "RX done: stay", egui::FontId::proportional(11.0), visuals.weak_text_color());
+            }
+        }
+
+        let txoff_mode = self.register_value.mcsm1 & 0x03;
+        let after_tx = match txoff_mode {
+            0 => Some(idle),
+            1 => Some(fstxon),
+            3 => Some(rx),
+            _ => None,
+        };
+        match after_tx {
+            Some(target) => arrow(tx + egui::vec2(10.0, 0.0), target + egui::vec2(10.0, 0.0), highlight, "TX done"),
+            None => {
+                painter.circle_stroke(tx + egui::vec2(48.0, 10.0), 10.0, highlight);
+                painter.text(tx + egui::vec2(0.0, 24.0), egui::Align2::CENTER_CENTER, "TX done: stay", egui::FontId::proportional(11.0), visuals.weak_text_color());
+            }
+        }
+
+        if autocal >= 2 {
+            painter.text(rect.left_bottom() + egui::vec2(4.0, -4.0), egui::Align2::LEFT_BOTTOM,
+                if autocal == 2 {"CAL when returning to IDLE"} else {"CAL every 4th return to IDLE"},
+                egui::FontId::proportional(11.0), visuals.weak_text_color());
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::time::{Duration, Instant};
+use eframe::egui;
+use crate::{conversions, format_value, PendingRead, SerialApp};
+
+// Registers that change while the radio runs, PARTNUM and VERSION are only read on request
+const POLLED_REGISTERS: [&str; 5] = ["RSSI", "LQI", "MARCSTATE", "PKTSTATUS", "FREQEST"];
+
+pub struct StatusMonitor {
+    pub polling: bool,
+    pub poll_interval_ms: u64,
+    last_poll: Instant,
+}
+
+impl StatusMonitor {
+    pub fn new() -> Self {
+        Self {
+            polling: false,
+            poll_interval_ms: 500,
+            last_poll: Instant::now(),
+        }
+    }
+}
+
+// RSSI is a two's complement value in half dB steps
+pub fn rssi_dbm(rssi: u8, rssi_offset: f64) -> f64 {
+    (rssi as i8) as f64 / 2.0 - rssi_offset
+}
+
+pub fn marcstate_name(marcstate: u8) -> &'static str {
+    match marcstate & 0x1F {
+        0x00 => "SLEEP",
+        0x01 => "IDLE",
+        0x03 => "VCOON_MC",
+        0x04 => "REGON_MC",
+        0x05 => "MANCAL",
+        0x06 => "VCOON",
+        0x07 => "REGON",
+        0x08 => "STARTCAL",
+        0x09 => "BWBOOST",
+        0x0A => "FS_LOCK",
+        0x0B => "IFADCON",
+        0x0C => "ENDCAL",
+        0x0D => "RX",
+        0x0E => "RX_END",
+        0x0F => "RX_RST",
+        0x10 => "TXRX_SWITCH",
+        0x11 => "RX_OVERFLOW",
+        0x12 => "FSTXON",
+        0x13 => "TX",
+        0x14 => "TX_END",
+        0x15 => "RXTX_SWITCH",
+        0x16 => "TX_UNDERFLOW",
+        _ => "Unknown",
+    }
+}
+
+impl SerialApp {
+    fn read_status_registers(&mut self, names: &[&'static str]) {
+        for (name, address) in self.chip.status_address.fields() {
+            if names.contains(&name) {
+                self.read_register(address, PendingRead::Status(name));
+            }
+        }
+    }
+
+    pub(crate) fn handle_status_register_read(&mut self, name: &'static str, value: u8) {
+        if let Some(register) = self.status_value.field_mut(name) {
+            *register = value;
+        }
+    }
+
+    pub(crate) fn poll_status_registers(&mut self, ctx: &egui::Context) {
+        if !self.status_monitor.polling || self.port.is_none() {
+            return;
+        }
+        let interval = Duration::from_millis(self.status_monitor.poll_interval_ms);
+        // don't queue another round while the last one is still being answered
+        let still_reading = self.pending_reads.iter().any(|pending| matches!(pending, PendingRead::Status(_)));
+        if self.status_monitor.last_poll.elapsed() >= interval && !still_reading {
+            self.status_monitor.last_poll = Instant::now();
+            self.read_status_registers(&POLLED_REGISTERS);
+        }
+        ctx.request_repaint_after(interval);
+    }
+
+    pub(crate) fn show_status_monitor(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Status");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.status_monitor.polling, "Poll every");
+            ui.add(egui::DragValue::new(&mut self.status_monitor.poll_interval_ms).range(50..=10_000).suffix(" ms"));
+            if ui.button("Read all").clicked() {
+                self.read_status_registers(&["PARTNUM", "VERSION", "RSSI", "LQI", "MARCSTATE", "PKTSTATUS", "FREQEST"]);
+            }
+        });
+        if self.port.is_none() {
+            ui.label("No radio connected");
+        }
+
+        let status = self.status_value;
+        egui::Grid::new("status_registers")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("RSSI");
+                ui.label(format!("{} dBm", format_value(rssi_dbm(status.rssi, self.chip.rssi_offset))));
+                ui.label(format!("0x{:02X}", status.rssi));
+                ui.end_row();
+
+                ui.label("LQI");
+                ui.label(format!("{}{}", status.lqi & 0x7F, if status.lqi & 0x80 != 0 {" (CRC OK)"} else {""}));
+                ui.label(format!("0x{:02X}", status.lqi));
+                ui.end_row();
+
+                ui.label("MARCSTATE");
+                ui.label(marcstate_name(status.marcstate));
+                ui.label(format!("0x{:02X}", status.marcstate));
+                ui.end_row();
+
+                ui.label("PKTSTATUS");
+                ui.label(format!("CRC_OK: {}, CS: {}, PQT_REACHED: {}, CCA: {}, SFD: {}",
+                    status.pktstatus >> 7 & 1,
+                    status.pktstatus >> 6 & 1,
+                    status.pktstatus >> 5 & 1,
+                    status.pktstatus >> 4 & 1,
+                    status.pktstatus >> 3 & 1));
+                ui.label(format!("0x{:02X}", status.pktstatus));
+                ui.end_row();
+
+                ui.label("FREQEST");
+                // FREQEST has the same two's complement f_xosc / 2^14 format as FSCTRL0.FREQOFF
+                ui.label(format!("{} kHz", format_value(conversions::register_to_frequency_offset(status.freqest, self.crystal_hz))));
+                ui.label(format!("0x{:02X}", status.freqest));
+                ui.end_row();
+
+                ui.label("PARTNUM");
+                ui.label(crate::chips::from_partnum(status.partnum).map_or("Unknown", |chip| chip.name));
+                ui.label(format!("0x{:02X}", status.partnum));
+                ui.end_row();
+
+                ui.label("VERSION");
+                ui.label(status.version.to_string());
+                ui.label(format!("0x{:02X}", status.version));
+                ui.end_row();
+            });
+    }
+}
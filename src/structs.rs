@@ -1,3 +1,5 @@
+use crate::conversions;
+
 // Name of all register addresses and their length (16 bits)
 pub struct RegisterAddress {
     pub iocfg2: u16,
@@ -38,10 +40,18 @@ pub struct RegisterAddress {
     pub test2: u16,
     pub test1: u16,
     pub test0: u16,
-    pub pa_table0: u16
+    pub pa_table0: u16,
+    pub pa_table1: u16,
+    pub pa_table2: u16,
+    pub pa_table3: u16,
+    pub pa_table4: u16,
+    pub pa_table5: u16,
+    pub pa_table6: u16,
+    pub pa_table7: u16,
 }
 
 // Register value struct declaration (this is the struct of the actual register values)
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
 pub struct RegisterValue {
     pub iocfg2: u8,
     pub iocfg1: u8,
@@ -81,5 +91,257 @@ pub struct RegisterValue {
     pub test2: u8,
     pub test1: u8,
     pub test0: u8,
-    pub pa_table0: u8
-}
\ No newline at end of file
+    pub pa_table0: u8,
+    pub pa_table1: u8,
+    pub pa_table2: u8,
+    pub pa_table3: u8,
+    pub pa_table4: u8,
+    pub pa_table5: u8,
+    pub pa_table6: u8,
+    pub pa_table7: u8,
+}
+// Read-only status registers, these are never written or saved with a configuration
+pub struct StatusRegisterAddress {
+    pub partnum: u16,
+    pub version: u16,
+    pub freqest: u16,
+    pub lqi: u16,
+    pub rssi: u16,
+    pub marcstate: u16,
+    pub pktstatus: u16,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct StatusRegisterValue {
+    pub partnum: u8,
+    pub version: u8,
+    pub freqest: u8,
+    pub lqi: u8,
+    pub rssi: u8,
+    pub marcstate: u8,
+    pub pktstatus: u8,
+}
+
+// Decoded values of the register fields, any configuration can be decoded given its crystal
+impl RegisterValue {
+    pub fn freq_word(&self) -> u32 {
+        ((self.freq2 as u32) << 16) | ((self.freq1 as u32) << 8) | self.freq0 as u32
+    }
+
+    pub fn base_frequency(&self, crystal_hz: u32) -> f64 {
+        conversions::register_to_frequency(self.freq_word(), crystal_hz)
+    }
+
+    pub fn deviation(&self, crystal_hz: u32) -> f64 {
+        let register_deviatn_m = self.deviatn & 0x07;
+        let register_deviatn_e = (self.deviatn & 0x70) >> 4;
+        conversions::register_to_deviation(register_deviatn_e, register_deviatn_m, crystal_hz)
+    }
+
+    pub fn data_rate(&self, crystal_hz: u32) -> f64 {
+        let register_dr_e = self.mdmcfg4 & 0x0F;
+        let register_dr_m = self.mdmcfg3;
+        conversions::register_to_data_rate(register_dr_e, register_dr_m, crystal_hz)
+    }
+
+    pub fn channel_bandwidth(&self, crystal_hz: u32) -> f64 {
+        let register_chanbw_e = self.mdmcfg4 >> 6;
+        let register_chanbw_m = (self.mdmcfg4 & 0x30) >> 4;
+        conversions::register_to_channel_bandwidth(register_chanbw_e, register_chanbw_m, crystal_hz)
+    }
+
+    // MSK always deviates by a quarter of the data rate, DEVIATN is not used for the deviation then
+    pub fn effective_deviation(&self, crystal_hz: u32) -> f64 {
+        if self.mdmcfg2 & 0x70 == 0x70 {
+            self.data_rate(crystal_hz) / 4.0
+        } else {
+            self.deviation(crystal_hz)
+        }
+    }
+
+    // h = 2 * deviation / data rate
+    pub fn modulation_index(&self, crystal_hz: u32) -> f64 {
+        2.0 * self.effective_deviation(crystal_hz) / self.data_rate(crystal_hz)
+    }
+
+    pub fn channel_spacing(&self, crystal_hz: u32) -> f64 {
+        let register_chanspc_e = self.mdmcfg1 & 0x03;
+        let register_chanspc_m = self.mdmcfg0;
+        conversions::register_to_channel_spacing(register_chanspc_e, register_chanspc_m, crystal_hz)
+    }
+
+    pub fn channel_frequency(&self, crystal_hz: u32) -> f64 {
+        conversions::register_to_channel_frequency(self.freq_word(), self.channr, self.mdmcfg1 & 0x03, self.mdmcfg0, crystal_hz)
+    }
+
+    pub fn modulation_name(&self) -> &'static str {
+        match (self.mdmcfg2 & 0x70) >> 4 {
+            0 => "2-FSK",
+            1 => "GFSK",
+            3 => "ASK/OOK",
+            7 => "MSK",
+            _ => "Reserved",
+        }
+    }
+
+    // FREND0.PA_POWER, the PA_TABLE entry used for TX (for ASK/OOK the entry sent for a 1)
+    pub fn pa_power(&self) -> u8 {
+        self.frend0 & 0x07
+    }
+
+    pub fn pa_table(&self) -> [u8; 8] {
+        [self.pa_table0, self.pa_table1, self.pa_table2, self.pa_table3,
+            self.pa_table4, self.pa_table5, self.pa_table6, self.pa_table7]
+    }
+
+    pub fn pa_table_entry_mut(&mut self, index: u8) -> &mut u8 {
+        match index & 0x07 {
+            0 => &mut self.pa_table0,
+            1 => &mut self.pa_table1,
+            2 => &mut self.pa_table2,
+            3 => &mut self.pa_table3,
+            4 => &mut self.pa_table4,
+            5 => &mut self.pa_table5,
+            6 => &mut self.pa_table6,
+            _ => &mut self.pa_table7,
+        }
+    }
+}
+
+// Lists every register once with its datasheet name, so code that needs to walk all registers
+// (config files, diffs, reading the radio) doesn't have to repeat the field list
+macro_rules! register_fields {
+    ($value:ident, $address:ident { $($field:ident => $name:literal),* $(,)? }) => {
+        // not every struct uses every accessor
+        #[allow(dead_code)]
+        impl $value {
+            pub fn fields(&self) -> Vec<(&'static str, u8)> {
+                vec![$(($name, self.$field)),*]
+            }
+
+            pub fn field(&self, name: &str) -> Option<u8> {
+                match name {
+                    $($name => Some(self.$field),)*
+                    _ => None,
+                }
+            }
+
+            pub fn field_mut(&mut self, name: &str) -> Option<&mut u8> {
+                match name {
+                    $($name => Some(&mut self.$field),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl $address {
+            pub fn fields(&self) -> Vec<(&'static str, u16)> {
+                vec![$(($name, self.$field)),*]
+            }
+        }
+    };
+}
+
+register_fields!(RegisterValue, RegisterAddress {
+    iocfg2 => "IOCFG2",
+    iocfg1 => "IOCFG1",
+    iocfg0 => "IOCFG0",
+    sync1 => "SYNC1",
+    sync0 => "SYNC0",
+    pktlen => "PKTLEN",
+    pktctrl1 => "PKTCTRL1",
+    pktctrl0 => "PKTCTRL0",
+    addr => "ADDR",
+    channr => "CHANNR",
+    fsctrl1 => "FSCTRL1",
+    fsctrl0 => "FSCTRL0",
+    freq2 => "FREQ2",
+    freq1 => "FREQ1",
+    freq0 => "FREQ0",
+    mdmcfg4 => "MDMCFG4",
+    mdmcfg3 => "MDMCFG3",
+    mdmcfg2 => "MDMCFG2",
+    mdmcfg1 => "MDMCFG1",
+    mdmcfg0 => "MDMCFG0",
+    deviatn => "DEVIATN",
+    mcsm2 => "MCSM2",
+    mcsm1 => "MCSM1",
+    mcsm0 => "MCSM0",
+    foccfg => "FOCCFG",
+    bscfg => "BSCFG",
+    agcctrl2 => "AGCCTRL2",
+    agcctrl1 => "AGCCTRL1",
+    agcctrl0 => "AGCCTRL0",
+    frend1 => "FREND1",
+    frend0 => "FREND0",
+    fscal3 => "FSCAL3",
+    fscal2 => "FSCAL2",
+    fscal1 => "FSCAL1",
+    fscal0 => "FSCAL0",
+    test2 => "TEST2",
+    test1 => "TEST1",
+    test0 => "TEST0",
+    pa_table0 => "PA_TABLE0",
+    pa_table1 => "PA_TABLE1",
+    pa_table2 => "PA_TABLE2",
+    pa_table3 => "PA_TABLE3",
+    pa_table4 => "PA_TABLE4",
+    pa_table5 => "PA_TABLE5",
+    pa_table6 => "PA_TABLE6",
+    pa_table7 => "PA_TABLE7",
+});
+
+register_fields!(StatusRegisterValue, StatusRegisterAddress {
+    partnum => "PARTNUM",
+    version => "VERSION",
+    freqest => "FREQEST",
+    lqi => "LQI",
+    rssi => "RSSI",
+    marcstate => "MARCSTATE",
+    pktstatus => "PKTSTATUS",
+});
+
+// Datasheet bit fields of each register as (name, high bit, low bit), registers without
+// named fields are one 8 bit field
+pub fn bit_fields(register: &str) -> &'static [(&'static str, u8, u8)] {
+    match register {
+        "IOCFG2" => &[("GDO2_INV", 6, 6), ("GDO2_CFG", 5, 0)],
+        "IOCFG1" => &[("GDO_DS", 7, 7), ("GDO1_INV", 6, 6), ("GDO1_CFG", 5, 0)],
+        "IOCFG0" => &[("GDO0_INV", 6, 6), ("GDO0_CFG", 5, 0)],
+        "PKTCTRL1" => &[("PQT", 7, 5), ("APPEND_STATUS", 2, 2), ("ADR_CHK", 1, 0)],
+        "PKTCTRL0" => &[("WHITE_DATA", 6, 6), ("PKT_FORMAT", 5, 4), ("CC2400_EN", 3, 3), ("CRC_EN", 2, 2), ("LENGTH_CONFIG", 1, 0)],
+        "FSCTRL1" => &[("FREQ_IF", 4, 0)],
+        "MDMCFG4" => &[("CHANBW_E", 7, 6), ("CHANBW_M", 5, 4), ("DRATE_E", 3, 0)],
+        "MDMCFG2" => &[("DEM_DCFILT_OFF", 7, 7), ("MOD_FORMAT", 6, 4), ("MANCHESTER_EN", 3, 3), ("SYNC_MODE", 2, 0)],
+        "MDMCFG1" => &[("FEC_EN", 7, 7), ("NUM_PREAMBLE", 6, 4), ("CHANSPC_E", 1, 0)],
+        "DEVIATN" => &[("DEVIATION_E", 6, 4), ("DEVIATION_M", 2, 0)],
+        "MCSM2" => &[("RX_TIME_RSSI", 4, 4), ("RX_TIME_QUAL", 3, 3), ("RX_TIME", 2, 0)],
+        "MCSM1" => &[("CCA_MODE", 5, 4), ("RXOFF_MODE", 3, 2), ("TXOFF_MODE", 1, 0)],
+        "MCSM0" => &[("FS_AUTOCAL", 5, 4)],
+        "FOCCFG" => &[("FOC_BS_CS_GATE", 5, 5), ("FOC_PRE_K", 4, 3), ("FOC_POST_K", 2, 2), ("FOC_LIMIT", 1, 0)],
+        "BSCFG" => &[("BS_PRE_KI", 7, 6), ("BS_PRE_KP", 5, 4), ("BS_POST_KI", 3, 3), ("BS_POST_KP", 2, 2), ("BS_LIMIT", 1, 0)],
+        "AGCCTRL2" => &[("MAX_DVGA_GAIN", 7, 6), ("MAX_LNA_GAIN", 5, 3), ("MAGN_TARGET", 2, 0)],
+        "AGCCTRL1" => &[("AGC_LNA_PRIORITY", 6, 6), ("CARRIER_SENSE_REL_THR", 5, 4), ("CARRIER_SENSE_ABS_THR", 3, 0)],
+        "AGCCTRL0" => &[("HYST_LEVEL", 7, 6), ("WAIT_TIME", 5, 4), ("AGC_FREEZE", 3, 2), ("FILTER_LENGTH", 1, 0)],
+        "FREND1" => &[("LNA_CURRENT", 7, 6), ("LNA2MIX_CURRENT", 5, 4), ("LODIV_BUF_CURRENT_RX", 3, 2), ("MIX_CURRENT", 1, 0)],
+        "FREND0" => &[("LODIV_BUF_CURRENT_TX", 5, 4), ("PA_POWER", 2, 0)],
+        "FSCAL3" => &[("FSCAL3", 7, 6), ("CHP_CURR_CAL_EN", 5, 4), ("FSCAL3", 3, 0)],
+        "FSCAL2" => &[("VCO_CORE_H_EN", 5, 5), ("FSCAL2", 4, 0)],
+        "FSCAL1" => &[("FSCAL1", 5, 0)],
+        "FSCAL0" => &[("FSCAL0", 6, 0)],
+        _ => &[("", 7, 0)],
+    }
+}
+
+// Names of the bit fields whose value differs between two values of the same register
+pub fn changed_bit_fields(register: &'static str, left: u8, right: u8) -> Vec<&'static str> {
+    let changed_bits = left ^ right;
+    bit_fields(register)
+        .iter()
+        .filter(|(_, high, low)| {
+            let mask = ((0xFF_u16 << (high + 1)) as u8) ^ (0xFF_u8 << low);
+            changed_bits & mask != 0
+        })
+        .map(|(name, _, _)| if name.is_empty() { register } else { name })
+        .collect()
+}
@@ -0,0 +1,129 @@
+use eframe::egui;
+use crate::{config_file, history, PendingRead, SerialApp};
+
+// One configuration being edited, only the tabs that aren't shown are kept here.
+// The shown tab's configuration and history live in SerialApp itself
+pub struct ConfigurationTab {
+    pub name: String,
+    pub snapshot: history::Snapshot,
+    history: history::History,
+}
+
+pub struct TabsState {
+    pub tabs: Vec<ConfigurationTab>,
+    pub active: usize,
+    next_number: usize,
+}
+
+impl TabsState {
+    pub fn new(initial: history::Snapshot) -> Self {
+        Self {
+            tabs: vec![ConfigurationTab { name: "Config 1".to_string(), snapshot: initial, history: history::History::new(initial) }],
+            active: 0,
+            next_number: 2,
+        }
+    }
+}
+
+impl SerialApp {
+    // The configuration of any tab, the active one is read straight from the editor
+    pub(crate) fn tab_configuration(&self, index: usize) -> Option<config_file::Configuration> {
+        let snapshot = if index == self.tabs.active {
+            self.snapshot()
+        } else {
+            self.tabs.tabs.get(index)?.snapshot
+        };
+        Some(config_file::Configuration {
+            chip_name: snapshot.chip.name.to_string(),
+            crystal_hz: snapshot.crystal_hz,
+            registers: snapshot.registers,
+        })
+    }
+
+    // Sets one register of a tab that isn't shown, recorded in that tab's own history
+    pub(crate) fn set_tab_register(&mut self, index: usize, name: &str, value: u8, description: String) {
+        let Some(tab) = self.tabs.tabs.get_mut(index) else {
+            return;
+        };
+        let before = tab.snapshot;
+        if let Some(register) = tab.snapshot.registers.field_mut(name) {
+            *register = value;
+        }
+        if tab.snapshot != before {
+            tab.history.push(description, tab.snapshot);
+        }
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.tabs.active || index >= self.tabs.tabs.len() {
+            return;
+        }
+        let snapshot = self.snapshot();
+        let active = &mut self.tabs.tabs[self.tabs.active];
+        active.snapshot = snapshot;
+        std::mem::swap(&mut active.history, &mut self.history);
+
+        let tab = &mut self.tabs.tabs[index];
+        std::mem::swap(&mut tab.history, &mut self.history);
+        let snapshot = tab.snapshot;
+        self.tabs.active = index;
+        self.register_value = snapshot.registers;
+        self.chip = snapshot.chip;
+        self.crystal_hz = snapshot.crystal_hz;
+        self.update_parameters_from_registers();
+    }
+
+    // New tabs start as a copy of the shown configuration
+    fn new_tab(&mut self) {
+        let snapshot = self.snapshot();
+        self.tabs.tabs.push(ConfigurationTab {
+            name: format!("Config {}", self.tabs.next_number),
+            snapshot,
+            history: history::History::new(snapshot),
+        });
+        self.tabs.next_number += 1;
+        self.switch_tab(self.tabs.tabs.len() - 1);
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.tabs.len() < 2 {
+            return;
+        }
+        if index == self.tabs.active {
+            self.switch_tab(if index == 0 { 1 } else { index - 1 });
+        }
+        self.tabs.tabs.remove(index);
+        if self.tabs.active > index {
+            self.tabs.active -= 1;
+        }
+        self.diff_tab_closed(index);
+    }
+
+    pub(crate) fn show_tab_bar(&mut self, ui: &mut egui::Ui) {
+        // replies of a radio read go into the shown tab, so it can't change until they are in
+        let reading_radio = self.pending_reads.iter().any(|pending| matches!(pending, PendingRead::Register(_)));
+        let mut switch_to = None;
+        let mut close = None;
+        ui.horizontal(|ui| {
+            for (index, tab) in self.tabs.tabs.iter().enumerate() {
+                ui.add_enabled_ui(!reading_radio, |ui| {
+                    if ui.selectable_label(index == self.tabs.active, &tab.name).clicked() {
+                        switch_to = Some(index);
+                    }
+                    if self.tabs.tabs.len() > 1 && ui.small_button("×").on_hover_text("Close tab").clicked() {
+                        close = Some(index);
+                    }
+                });
+            }
+            if ui.add_enabled(!reading_radio, egui::Button::new("+")).on_hover_text("New tab with a copy of this configuration").clicked() {
+                self.new_tab();
+            }
+        });
+        if let Some(index) = switch_to {
+            self.switch_tab(index);
+        }
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+    }
+}
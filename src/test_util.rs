@@ -0,0 +1,9 @@
+// Constants and checks shared by the test modules
+
+pub const XOSC_24_MHZ: u32 = 24_000_000;
+pub const XOSC_26_MHZ: u32 = 26_000_000;
+pub const XOSC_27_MHZ: u32 = 27_000_000;
+
+pub fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!((actual - expected).abs() <= tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+}
@@ -0,0 +1,262 @@
+use eframe::egui;
+use crate::{chips, conversions, format_value, SerialApp};
+use crate::structs::{self, RegisterValue};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+// One broken datasheet constraint, `fields` are the register bit fields that take part in it
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+    pub fields: &'static [&'static str],
+}
+
+fn problem(severity: Severity, message: String, fields: &'static [&'static str]) -> Problem {
+    Problem { severity, message, fields }
+}
+
+// (MOD_FORMAT, min kBaud, max kBaud) from the datasheet's RF specifications
+const DATA_RATE_LIMITS: [(u8, f64, f64); 4] = [
+    (0, 1.2, 500.0),
+    (1, 1.2, 250.0),
+    (3, 1.2, 250.0),
+    (7, 26.0, 500.0),
+];
+
+// Every datasheet constraint that depends on more than one field, errors first
+pub fn check(registers: &RegisterValue, chip: &chips::ChipProfile, crystal_hz: u32) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mod_format = (registers.mdmcfg2 & 0x70) >> 4;
+    let modulation = registers.modulation_name();
+    let manchester = registers.mdmcfg2 & 0x08 != 0;
+    let data_rate = registers.data_rate(crystal_hz);
+    let length_config = registers.pktctrl0 & 0x03;
+    let pkt_format = (registers.pktctrl0 & 0x30) >> 4;
+    let address_check = registers.pktctrl1 & 0x03;
+    let append_status = registers.pktctrl1 & 0x04 != 0;
+
+    // modulation
+    match DATA_RATE_LIMITS.iter().find(|(format, _, _)| *format == mod_format) {
+        Some((_, min, max)) => {
+            if data_rate < *min || data_rate > *max {
+                problems.push(problem(Severity::Error,
+                    format!("{} supports {} to {} kBaud, the data rate is {} kBaud", modulation, min, max, format_value(data_rate)),
+                    &["MOD_FORMAT", "DRATE_E", "DRATE_M"]));
+            }
+        }
+        None => problems.push(problem(Severity::Error, format!("MOD_FORMAT {} is reserved", mod_format), &["MOD_FORMAT"])),
+    }
+    if mod_format == 3 && !chip.supports_ask_ook {
+        problems.push(problem(Severity::Error, format!("The {} doesn't support ASK/OOK", chip.name), &["MOD_FORMAT"]));
+    }
+    if manchester && mod_format == 7 {
+        problems.push(problem(Severity::Error, "Manchester encoding can't be used with MSK".to_string(), &["MOD_FORMAT", "MANCHESTER_EN"]));
+    }
+    if mod_format == 3 && registers.foccfg & 0x03 != 0 {
+        problems.push(problem(Severity::Warning, "Frequency offset compensation is not supported with ASK/OOK, use no compensation".to_string(), &["MOD_FORMAT", "FOC_LIMIT"]));
+    }
+
+    // bandwidth
+    let signal_bandwidth = conversions::signal_bandwidth(data_rate, registers.effective_deviation(crystal_hz));
+    if registers.channel_bandwidth(crystal_hz) < signal_bandwidth {
+        problems.push(problem(Severity::Warning,
+            format!("Channel bandwidth is narrower than the {} kHz signal (data rate + 2 × deviation)", format_value(signal_bandwidth)),
+            &["CHANBW_E", "CHANBW_M", "DRATE_E", "DRATE_M", "DEVIATION_E", "DEVIATION_M"]));
+    }
+
+    // packet handling
+    if registers.pktctrl0 & 0x40 != 0 && pkt_format != 0 {
+        problems.push(problem(Severity::Warning, "Data whitening only applies to packets in normal (FIFO) mode".to_string(), &["WHITE_DATA", "PKT_FORMAT"]));
+    }
+    if length_config == 3 {
+        problems.push(problem(Severity::Error, "LENGTH_CONFIG 3 is reserved".to_string(), &["LENGTH_CONFIG"]));
+    }
+    if length_config == 0 && registers.pktlen == 0 {
+        problems.push(problem(Severity::Error, "Fixed length packets need PKTLEN above 0".to_string(), &["LENGTH_CONFIG", "PKTLEN"]));
+    }
+    if length_config == 1 && registers.pktlen == 0 {
+        problems.push(problem(Severity::Error, "Variable length with a maximum PKTLEN of 0 rejects every packet".to_string(), &["LENGTH_CONFIG", "PKTLEN"]));
+    }
+    if length_config == 2 && append_status {
+        problems.push(problem(Severity::Warning, "Status bytes are never appended in infinite length mode".to_string(), &["LENGTH_CONFIG", "APPEND_STATUS"]));
+    }
+    if (address_check >= 2 && registers.addr == 0x00) || (address_check == 3 && registers.addr == 0xFF) {
+        problems.push(problem(Severity::Warning, format!("Device address 0x{:02X} is also a broadcast address", registers.addr), &["ADR_CHK", "ADDR"]));
+    }
+
+    problems.sort_by_key(|problem| problem.severity != Severity::Error);
+    problems
+}
+
+// Rows of the left panel and the bit fields (or whole registers) they edit
+const PARAMETER_ROWS: [(&str, &[&str]); 22] = [
+    ("Base Frequency", &["FREQ2", "FREQ1", "FREQ0"]),
+    ("Channel Number", &["CHANNR"]),
+    ("Modulation Scheme", &["MOD_FORMAT"]),
+    ("Data Whitening", &["WHITE_DATA"]),
+    ("Manchester Enable", &["MANCHESTER_EN"]),
+    ("TX Power", &["PA_POWER"]),
+    ("Deviation", &["DEVIATION_E", "DEVIATION_M"]),
+    ("Data Rate", &["DRATE_E", "DRATE_M"]),
+    ("Channel Bandwidth", &["CHANBW_E", "CHANBW_M"]),
+    ("Channel Spacing", &["CHANSPC_E", "CHANSPC_M"]),
+    ("IF Frequency", &["FREQ_IF"]),
+    ("Frequency Offset", &["FSCTRL0"]),
+    ("Sync Word", &["SYNC1", "SYNC0"]),
+    ("Sync Mode", &["SYNC_MODE"]),
+    ("Preamble", &["NUM_PREAMBLE"]),
+    ("Packet Length Mode", &["LENGTH_CONFIG"]),
+    ("Packet Length", &["PKTLEN"]),
+    ("CRC", &["CRC_EN"]),
+    ("Append Status", &["APPEND_STATUS"]),
+    ("Address Check", &["ADR_CHK"]),
+    ("Device Address", &["ADDR"]),
+    ("Preamble Quality Threshold", &["PQT"]),
+];
+
+// REGISTER.FIELD for a bit field name, whole register names are returned as they are
+fn field_location(registers: &RegisterValue, field: &str) -> String {
+    registers
+        .fields()
+        .into_iter()
+        .find(|(register, _)| structs::bit_fields(register).iter().any(|(name, _, _)| *name == field))
+        .map_or(field.to_string(), |(register, _)| format!("{}.{}", register, field))
+}
+
+impl SerialApp {
+    // First column of a left panel row, highlighted (and scrolled to once) while a problem involving its fields is selected
+    pub(crate) fn parameter_label(&mut self, ui: &mut egui::Ui, label: &str) {
+        let highlighted = PARAMETER_ROWS
+            .iter()
+            .any(|(row, fields)| *row == label && fields.iter().any(|field| self.highlighted_fields.contains(field)));
+        if !highlighted {
+            ui.label(label);
+            return;
+        }
+        let response = ui.label(egui::RichText::new(label).strong().color(ui.visuals().warn_fg_color));
+        if self.scroll_to_highlighted_field {
+            response.scroll_to_me(Some(egui::Align::Center));
+            self.scroll_to_highlighted_field = false;
+        }
+    }
+
+    fn select_problem(&mut self, problem: &Problem) {
+        if self.highlighted_fields == problem.fields {
+            self.highlighted_fields = &[];
+            return;
+        }
+        self.highlighted_fields = problem.fields;
+        self.scroll_to_highlighted_field = true;
+        // frequency offset compensation is only edited in its own window
+        if problem.fields.iter().any(|field| field.starts_with("FOC_")) {
+            self.foc_view.open = true;
+        }
+    }
+
+    pub(crate) fn show_warnings_panel(&mut self, ui: &mut egui::Ui) {
+        let problems = check(&self.register_value, self.chip, self.crystal_hz);
+        // the highlight goes away with the problem once it is fixed
+        if !problems.iter().any(|problem| problem.fields == self.highlighted_fields) {
+            self.highlighted_fields = &[];
+        }
+        let errors = problems.iter().filter(|problem| problem.severity == Severity::Error).count();
+        ui.strong(format!("Configuration check: {} errors, {} warnings", errors, problems.len() - errors));
+        let mut selected = None;
+        egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+            for (index, problem) in problems.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let (label, color) = match problem.severity {
+                        Severity::Error => ("Error", ui.visuals().error_fg_color),
+                        Severity::Warning => ("Warning", ui.visuals().warn_fg_color),
+                    };
+                    ui.colored_label(color, label);
+                    ui.label(&problem.message);
+                    let fields: Vec<String> = problem.fields.iter().map(|field| field_location(&self.register_value, field)).collect();
+                    let is_selected = problem.fields == self.highlighted_fields;
+                    if ui.selectable_label(is_selected, fields.join(", ")).on_hover_text("Highlight these fields").clicked() {
+                        selected = Some(index);
+                    }
+                });
+            }
+        });
+        if let Some(index) = selected {
+            self.select_problem(&problems[index]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XOSC_26_MHZ;
+
+    // The editor's start-up configuration with a 325 kHz channel filter, which breaks no rule
+    fn valid_registers() -> RegisterValue {
+        RegisterValue {
+            pktlen: 0xFF,
+            pktctrl1: 0x04,
+            pktctrl0: 0x45,
+            freq2: 0x5E,
+            freq1: 0xC4,
+            freq0: 0xEC,
+            mdmcfg4: 0x5C,
+            mdmcfg3: 0x22,
+            mdmcfg2: 0x02,
+            mdmcfg1: 0x22,
+            mdmcfg0: 0xF8,
+            deviatn: 0x47,
+            foccfg: 0x76,
+            ..Default::default()
+        }
+    }
+
+    fn messages(problems: &[Problem]) -> Vec<&str> {
+        problems.iter().map(|problem| problem.message.as_str()).collect()
+    }
+
+    #[test]
+    fn valid_configuration_has_no_problems() {
+        let problems = check(&valid_registers(), &chips::CC2510, XOSC_26_MHZ);
+        assert!(problems.is_empty(), "{:?}", messages(&problems));
+    }
+
+    #[test]
+    fn reserved_length_config_is_an_error() {
+        let registers = RegisterValue { pktctrl0: 0x47, ..valid_registers() };
+        let problems = check(&registers, &chips::CC2510, XOSC_26_MHZ);
+        assert_eq!(problems.len(), 1, "{:?}", messages(&problems));
+        assert!(problems[0].severity == Severity::Error);
+        assert_eq!(problems[0].fields, ["LENGTH_CONFIG"]);
+    }
+
+    #[test]
+    fn manchester_with_msk_is_an_error() {
+        let registers = RegisterValue { mdmcfg2: 0x7A, ..valid_registers() };
+        let problems = check(&registers, &chips::CC2510, XOSC_26_MHZ);
+        assert_eq!(messages(&problems), ["Manchester encoding can't be used with MSK"]);
+        assert!(problems[0].severity == Severity::Error);
+    }
+
+    #[test]
+    fn narrow_channel_filter_is_a_warning() {
+        // 203 kHz filter for a 115 kBaud signal with 47.6 kHz deviation
+        let registers = RegisterValue { mdmcfg4: 0x8C, ..valid_registers() };
+        let problems = check(&registers, &chips::CC2510, XOSC_26_MHZ);
+        assert_eq!(problems.len(), 1, "{:?}", messages(&problems));
+        assert!(problems[0].severity == Severity::Warning);
+        assert!(problems[0].fields.contains(&"CHANBW_E"));
+    }
+
+    #[test]
+    fn errors_come_before_warnings() {
+        let registers = RegisterValue { mdmcfg4: 0x8C, pktctrl0: 0x44, pktlen: 0, ..valid_registers() };
+        let problems = check(&registers, &chips::CC2510, XOSC_26_MHZ);
+        let severities: Vec<bool> = problems.iter().map(|problem| problem.severity == Severity::Error).collect();
+        assert_eq!(severities, [true, false]);
+        assert_eq!(problems[0].fields, ["LENGTH_CONFIG", "PKTLEN"]);
+    }
+}